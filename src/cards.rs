@@ -0,0 +1,231 @@
+//! Cards for a game in the manner of Eleusis: each card played onto the
+//! sequence is acceptable or not according to how it relates to the card
+//! before it.
+
+use std::fmt;
+use std::slice;
+
+use ansi_term;
+use rand::Rng;
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades
+}
+
+pub static SUITS: [Suit; 4] = [
+    Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+impl Suit {
+    fn symbol(&self) -> char {
+        match *self {
+            Suit::Clubs => '♣',
+            Suit::Diamonds => '♦',
+            Suit::Hearts => '♥',
+            Suit::Spades => '♠'
+        }
+    }
+
+    fn initial(&self) -> char {
+        match *self {
+            Suit::Clubs => 'C',
+            Suit::Diamonds => 'D',
+            Suit::Hearts => 'H',
+            Suit::Spades => 'S'
+        }
+    }
+
+    pub fn is_red(&self) -> bool {
+        match *self {
+            Suit::Diamonds | Suit::Hearts => true,
+            Suit::Clubs | Suit::Spades => false
+        }
+    }
+
+    pub fn iter() -> slice::Iter<'static, Self> {
+        SUITS.iter()
+    }
+
+    pub fn sample<R: Rng>(rng: &mut R) -> Self {
+        let index = rng.gen_range(0, 4);
+        SUITS[index]
+    }
+}
+
+
+/// ranks run from ace (1, low) to king (13)
+pub const KING: u8 = 13;
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct Card {
+    pub rank: u8,
+    pub suit: Suit
+}
+
+impl Card {
+    pub fn new(rank: u8, suit: Suit) -> Self {
+        Card { rank, suit }
+    }
+
+    pub fn sample<R: Rng>(rng: &mut R) -> Self {
+        let rank = rng.gen_range(1, KING + 1);
+        Card::new(rank, Suit::sample(rng))
+    }
+
+    /// all fifty-two cards
+    pub fn deck() -> Vec<Self> {
+        Suit::iter()
+            .flat_map(|&suit| (1..KING + 1).map(move |rank| {
+                Card::new(rank, suit)
+            }))
+            .collect()
+    }
+
+    fn rank_name(&self) -> String {
+        match self.rank {
+            1 => "A".to_owned(),
+            11 => "J".to_owned(),
+            12 => "Q".to_owned(),
+            13 => "K".to_owned(),
+            rank => format!("{}", rank)
+        }
+    }
+
+    /// The card in compact notation: its rank (`A`, `2`–`10`, `J`, `Q`, or
+    /// `K`) followed by the initial of its suit (e.g., `10H`, `QS`).
+    pub fn to_notation(self) -> String {
+        format!("{}{}", self.rank_name(), self.suit.initial())
+    }
+
+    pub fn from_notation(token: &str) -> Result<Self, String> {
+        let token = token.trim();
+        let last = token.chars().last();
+        let suit = match last {
+            Some('C') | Some('c') | Some('♣') => Suit::Clubs,
+            Some('D') | Some('d') | Some('♦') => Suit::Diamonds,
+            Some('H') | Some('h') | Some('♥') => Suit::Hearts,
+            Some('S') | Some('s') | Some('♠') => Suit::Spades,
+            Some(other) => {
+                return Err(format!("unknown suit {:?} in {:?} (expected \
+                                    C, D, H, or S)", other, token));
+            },
+            None => { return Err("expected a card".to_owned()); }
+        };
+        let rank_notation = &token[..token.len() -
+                                   last.map_or(0, |c| c.len_utf8())];
+        let rank = match &rank_notation.to_uppercase()[..] {
+            "A" => 1,
+            "J" => 11,
+            "Q" => 12,
+            "K" => 13,
+            number => match number.parse::<u8>() {
+                Ok(rank) if (2..=10).contains(&rank) => rank,
+                _ => {
+                    return Err(format!("unknown rank {:?} in {:?} \
+                                        (expected A, 2–10, J, Q, or K)",
+                                       rank_notation, token));
+                }
+            }
+        };
+        Ok(Card::new(rank, suit))
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let face = format!("{}{}", self.rank_name(), self.suit.symbol());
+        if self.suit.is_red() {
+            write!(f, "{}", ansi_term::Colour::Red.paint(&face))
+        } else {
+            write!(f, "{}", face)
+        }
+    }
+}
+
+
+/// a card played onto the sequence, together with the card it would follow
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct Succession {
+    pub previous: Card,
+    pub card: Card
+}
+
+impl Succession {
+    pub fn new(previous: Card, card: Card) -> Self {
+        Succession { previous, card }
+    }
+
+    pub fn sample<R: Rng>(rng: &mut R) -> Self {
+        let previous = Card::sample(rng);
+        Succession::new(previous, Card::sample(rng))
+    }
+
+    /// every ordered pair of cards (including a card following itself, as
+    /// from another deck)
+    pub fn enumerate() -> Vec<Self> {
+        let deck = Card::deck();
+        deck.iter()
+            .flat_map(|&previous| deck.iter().map(move |&card| {
+                Succession::new(previous, card)
+            }))
+            .collect()
+    }
+
+    /// the difference in rank between the card and the one before it
+    pub fn rank_distance(&self) -> u8 {
+        self.card.rank.abs_diff(self.previous.rank)
+    }
+
+    /// The succession in compact notation: the earlier card, then the later
+    /// (e.g., `QH 7S` for the seven of spades following the queen of hearts).
+    pub fn to_notation(self) -> String {
+        format!("{} {}", self.previous.to_notation(), self.card.to_notation())
+    }
+
+    pub fn from_notation(notation: &str) -> Result<Self, String> {
+        let tokens = notation.split_whitespace().collect::<Vec<_>>();
+        if tokens.len() != 2 {
+            return Err(format!("expected two cards in {:?}", notation));
+        }
+        Ok(Succession::new(Card::from_notation(tokens[0])?,
+                           Card::from_notation(tokens[1])?))
+    }
+}
+
+impl fmt::Display for Succession {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} after {}", self.card, self.previous)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concerning_notation() {
+        let succession = Succession::new(Card::new(12, Suit::Hearts),
+                                         Card::new(10, Suit::Spades));
+        assert_eq!("QH 10S", succession.to_notation());
+        assert_eq!(Ok(succession), Succession::from_notation("qh 10♠"));
+        assert_eq!(Ok(Card::new(1, Suit::Clubs)), Card::from_notation("AC"));
+        assert!(Card::from_notation("1C").is_err());
+        assert!(Card::from_notation("KX").is_err());
+        assert!(Succession::from_notation("KS").is_err());
+    }
+
+    #[test]
+    fn concerning_the_deck() {
+        assert_eq!(52, Card::deck().len());
+        assert_eq!(52 * 52, Succession::enumerate().len());
+        assert_eq!(9, Succession::from_notation("2D JC").unwrap()
+                   .rank_distance());
+    }
+
+}
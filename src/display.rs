@@ -8,9 +8,14 @@ macro_rules! wrapln {
         let formatted = format!($($arg)*);
         let mut splitten = ("", &formatted[..]);
         while splitten.1.len() > wrap_width {
-            let split_index = match splitten.1[..wrap_width].rfind(' ') {
+            // (not splitting a multibyte character, such as a card's suit)
+            let mut limit = wrap_width;
+            while !splitten.1.is_char_boundary(limit) {
+                limit -= 1;
+            }
+            let split_index = match splitten.1[..limit].rfind(' ') {
                 Some(index) => index + 1,  // split after the space
-                None => wrap_width  // oh well
+                None => limit  // oh well
             };
             splitten = splitten.1.split_at(split_index);
             println!("{}", splitten.0);
@@ -130,10 +135,97 @@ pub fn pack_blocks_horizontally(left_block: &str,
 }
 
 
+/// the CSS color for the foreground color selected by an ANSI SGR parameter
+fn css_color(sgr_parameter: &str) -> Option<&'static str> {
+    match sgr_parameter {
+        "30" => Some("black"),
+        "31" => Some("#e03c31"),
+        "32" => Some("#3cb043"),
+        "33" => Some("#f2c80f"),
+        "34" => Some("#3c78e0"),
+        "35" => Some("#c040c0"),
+        "36" => Some("#30b8c8"),
+        "37" => Some("#e8e8e8"),
+        _ => None
+    }
+}
+
+fn push_html_escaped(html: &mut String, codepoint: char) {
+    match codepoint {
+        '&' => html.push_str("&amp;"),
+        '<' => html.push_str("&lt;"),
+        '>' => html.push_str("&gt;"),
+        '"' => html.push_str("&quot;"),
+        _ => html.push(codepoint)
+    }
+}
+
+/// text with the ANSI escape sequences removed
+pub fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::new();
+    let mut in_escape_sequence = false;
+    for codepoint in text.chars() {
+        if in_escape_sequence {
+            if codepoint == 'm' {
+                in_escape_sequence = false;
+            }
+        } else if codepoint == '\u{1b}' {
+            in_escape_sequence = true;
+        } else {
+            stripped.push(codepoint);
+        }
+    }
+    stripped
+}
+
+/// text (escaped for HTML) with its ANSI foreground colors translated into
+/// styled spans
+pub fn ansi_to_html(text: &str) -> String {
+    let mut html = String::new();
+    let mut in_span = false;
+    let mut escape_sequence: Option<String> = None;
+    for codepoint in text.chars() {
+        match escape_sequence.take() {
+            Some(mut sequence) => {
+                if codepoint != 'm' {
+                    sequence.push(codepoint);
+                    escape_sequence = Some(sequence);
+                    continue;
+                }
+                if in_span {
+                    html.push_str("</span>");
+                    in_span = false;
+                }
+                let color = sequence.trim_start_matches('[').split(';')
+                    .filter_map(css_color)
+                    .next_back();
+                if let Some(color) = color {
+                    html.push_str(
+                        &format!("<span style=\"color: {}\">", color));
+                    in_span = true;
+                }
+            },
+            None => {
+                if codepoint == '\u{1b}' {
+                    escape_sequence = Some(String::new());
+                } else {
+                    push_html_escaped(&mut html, codepoint);
+                }
+            }
+        }
+    }
+    if in_span {
+        html.push_str("</span>");
+    }
+    html
+}
+
+
 #[cfg(test)]
 mod tests {
     use ansi_term;
-    use super::{block_dimensions, visualen, pack_blocks_vertically};
+    use super::{ansi_to_html, block_dimensions, strip_ansi, visualen,
+                pack_blocks_vertically};
 
     #[test]
     fn concerning_text_block_dimensions() {
@@ -162,4 +254,15 @@ mod tests {
         assert_eq!(12, visualen(&codetext));
     }
 
+    #[test]
+    fn concerning_translating_control_codes_for_other_media() {
+        let codetext = format!("{} & {}",
+                               ansi_term::Colour::Red.paint("<red>"),
+                               ansi_term::Colour::Blue.paint("blue"));
+        assert_eq!("<red> & blue", strip_ansi(&codetext));
+        assert_eq!("<span style=\"color: #e03c31\">&lt;red&gt;</span> &amp; \
+                    <span style=\"color: #3c78e0\">blue</span>",
+                   ansi_to_html(&codetext));
+    }
+
 }
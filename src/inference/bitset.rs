@@ -0,0 +1,78 @@
+use std::iter::FromIterator;
+
+
+const WORD_BITS: usize = 64;
+
+
+/// a fixed-length set of small nonnegative integers, packed into words so
+/// that intersections and cardinalities can be taken a word at a time
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct Bitset {
+    words: Vec<u64>
+}
+
+impl Bitset {
+    pub fn new(len: usize) -> Self {
+        let word_count = len.div_ceil(WORD_BITS);
+        Bitset { words: vec![0; word_count] }
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        self.words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+    }
+
+    pub fn set(&mut self, index: usize) {
+        self.words[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// the cardinality of the intersection of this set with another,
+    /// without allocating the intersection itself
+    pub fn count_ones_within(&self, mask: &Bitset) -> usize {
+        self.words.iter().zip(mask.words.iter())
+            .map(|(word, mask_word)| (word & mask_word).count_ones() as usize)
+            .sum()
+    }
+}
+
+
+impl FromIterator<bool> for Bitset {
+    fn from_iter<I: IntoIterator<Item=bool>>(iterable: I) -> Self {
+        let mut words = Vec::new();
+        for (len, member) in iterable.into_iter().enumerate() {
+            if len % WORD_BITS == 0 {
+                words.push(0);
+            }
+            if member {
+                *words.last_mut().expect("should have a word") |=
+                    1 << (len % WORD_BITS);
+            }
+        }
+        Bitset { words }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Bitset;
+
+    #[test]
+    fn concerning_counting_members_across_word_boundaries() {
+        let bitset = (0..150).map(|i| i % 3 == 0).collect::<Bitset>();
+        assert_eq!(50, bitset.count_ones());
+        assert!((0..150).all(|i| bitset.get(i) == (i % 3 == 0)));
+
+        let evens = (0..150).map(|i| i % 2 == 0).collect::<Bitset>();
+        assert_eq!(25, bitset.count_ones_within(&evens));
+
+        let mut empty = Bitset::new(150);
+        assert_eq!(0, empty.count_ones());
+        empty.set(149);
+        assert!(empty.get(149) && !empty.get(85));
+    }
+
+}
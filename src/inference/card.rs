@@ -0,0 +1,257 @@
+use rand::Rng;
+
+use cards::Succession;
+use inference::distribution;
+use inference::distribution::Domain;
+use inference::hypothesis;
+use inference::hypothesis::joined_space;
+pub use inference::hypothesis::Hypothesis;
+
+
+pub type Study = Succession;
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct SuitHypothesis {
+    pub same: bool
+}
+
+impl SuitHypothesis {
+    pub fn new(same: bool) -> Self {
+        SuitHypothesis { same }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Suit(self)
+    }
+}
+
+impl Hypothesis<Study> for SuitHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        (study.card.suit == study.previous.suit) == self.same
+    }
+
+    fn description(&self) -> String {
+        if self.same {
+            "it is of the same suit as the previous card".to_owned()
+        } else {
+            "it is of a different suit from the previous card".to_owned()
+        }
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct ColorHypothesis {
+    pub alternating: bool
+}
+
+impl ColorHypothesis {
+    pub fn new(alternating: bool) -> Self {
+        ColorHypothesis { alternating }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Color(self)
+    }
+}
+
+impl Hypothesis<Study> for ColorHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        (study.card.suit.is_red() != study.previous.suit.is_red()) ==
+            self.alternating
+    }
+
+    fn description(&self) -> String {
+        if self.alternating {
+            "it is of the other color from the previous card".to_owned()
+        } else {
+            "it is of the same color as the previous card".to_owned()
+        }
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct RankOrderHypothesis {
+    pub higher: bool
+}
+
+impl RankOrderHypothesis {
+    pub fn new(higher: bool) -> Self {
+        RankOrderHypothesis { higher }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::RankOrder(self)
+    }
+}
+
+impl Hypothesis<Study> for RankOrderHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        if self.higher {
+            study.card.rank > study.previous.rank
+        } else {
+            study.card.rank < study.previous.rank
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("it is {} in rank than the previous card",
+                if self.higher { "higher" } else { "lower" })
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct RankDistanceHypothesis {
+    pub at_most: u8
+}
+
+impl RankDistanceHypothesis {
+    pub fn new(at_most: u8) -> Self {
+        RankDistanceHypothesis { at_most }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::RankDistance(self)
+    }
+}
+
+impl Hypothesis<Study> for RankDistanceHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        study.rank_distance() <= self.at_most
+    }
+
+    fn description(&self) -> String {
+        if self.at_most == 0 {
+            "it is of the same rank as the previous card".to_owned()
+        } else {
+            format!("its rank differs from the previous card's by at most {}",
+                    self.at_most)
+        }
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum BasicHypothesis {
+    Suit(SuitHypothesis),
+    Color(ColorHypothesis),
+    RankOrder(RankOrderHypothesis),
+    RankDistance(RankDistanceHypothesis)
+}
+
+impl Hypothesis<Study> for BasicHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        match *self {
+            BasicHypothesis::Suit(h) => h.predicts_the_property(study),
+            BasicHypothesis::Color(h) => h.predicts_the_property(study),
+            BasicHypothesis::RankOrder(h) => h.predicts_the_property(study),
+            BasicHypothesis::RankDistance(h) => h.predicts_the_property(study)
+        }
+    }
+    fn description(&self) -> String {
+        match *self {
+            BasicHypothesis::Suit(h) => h.description(),
+            BasicHypothesis::Color(h) => h.description(),
+            BasicHypothesis::RankOrder(h) => h.description(),
+            BasicHypothesis::RankDistance(h) => h.description()
+        }
+    }
+}
+
+
+/// a basic criterion for a card following another, or the conjunction or
+/// disjunction of two
+pub type JoinedHypothesis = hypothesis::JoinedHypothesis<BasicHypothesis>;
+
+pub type Distribution<H> = distribution::Distribution<H, Study>;
+
+
+/// The largest rank distance to entertain as a bound: any wider, and the
+/// criterion would hardly ever be violated.
+const MAX_RANK_DISTANCE: u8 = 4;
+
+pub fn standard_basic_hypotheses() -> Vec<BasicHypothesis> {
+    let mut basics = Vec::new();
+    for &flag in &[true, false] {
+        basics.push(SuitHypothesis::new(flag).to_basic());
+        basics.push(ColorHypothesis::new(flag).to_basic());
+        basics.push(RankOrderHypothesis::new(flag).to_basic());
+    }
+    for at_most in 0..MAX_RANK_DISTANCE + 1 {
+        basics.push(RankDistanceHypothesis::new(at_most).to_basic());
+    }
+    basics
+}
+
+/// the standard criteria, and their conjunctions and disjunctions (omitting
+/// any that would accept every card or none, or that accepts just the same
+/// successions as one already included)
+pub fn hypothesis_space() -> Vec<JoinedHypothesis> {
+    joined_space(&standard_basic_hypotheses(), Vec::new(),
+                 &Succession::enumerate())
+}
+
+
+/// which card may follow which
+pub struct CardDomain;
+
+impl Domain for CardDomain {
+    type Study = Study;
+    type Hypothesis = JoinedHypothesis;
+
+    fn sample_study<R: Rng>(&self, rng: &mut R) -> Study {
+        Succession::sample(rng)
+    }
+
+    fn enumerate_studies(&self) -> Option<Vec<Study>> {
+        Some(Succession::enumerate())
+    }
+
+    fn prior<R: Rng>(&self, _rng: &mut R) -> Distribution<JoinedHypothesis> {
+        Distribution::ignorance_prior(hypothesis_space())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cards::Succession;
+
+    #[test]
+    fn concerning_criteria_for_succession() {
+        let study = Succession::from_notation("QH 10H").unwrap();
+        assert!(SuitHypothesis::new(true).predicts_the_property(&study));
+        assert!(!ColorHypothesis::new(true).predicts_the_property(&study));
+        assert!(RankOrderHypothesis::new(false).predicts_the_property(&study));
+        assert!(RankDistanceHypothesis::new(2).predicts_the_property(&study));
+        assert!(!RankDistanceHypothesis::new(1).predicts_the_property(&study));
+        assert_eq!("it is of the same color as the previous card",
+                   ColorHypothesis::new(false).description());
+    }
+
+    #[test]
+    fn concerning_the_hypothesis_space() {
+        let hypotheses = hypothesis_space();
+        // (a card of the same suit is of the same color, so their
+        // conjunction is just the suit criterion again and their disjunction
+        // just the color criterion, while a card of the other color can't be
+        // of the same suit at all)
+        let same_suit = SuitHypothesis::new(true).to_basic();
+        let same_color = ColorHypothesis::new(false).to_basic();
+        let alternating = ColorHypothesis::new(true).to_basic();
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(same_suit)));
+        assert!(!hypotheses.contains(
+            &JoinedHypothesis::and(same_suit, same_color)));
+        assert!(!hypotheses.contains(
+            &JoinedHypothesis::or(same_suit, same_color)));
+        assert!(!hypotheses.contains(
+            &JoinedHypothesis::and(same_suit, alternating)));
+        assert!(hypotheses.contains(&JoinedHypothesis::or(
+            same_suit, RankOrderHypothesis::new(true).to_basic())));
+    }
+
+}
@@ -0,0 +1,415 @@
+use std::cmp;
+use std::collections::BTreeMap;
+use std::cmp::{Ord, Ordering};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::time::Instant;
+
+use ansi_term::Style;
+use rand::Rng;
+
+use inference::bitset::Bitset;
+use inference::hypothesis::Hypothesis;
+use inference::parallel::parallel_map;
+use inference::prediction_matrix::{Lookahead, PredictionMatrix};
+use inference::strategy::{Split, Strategy};
+
+
+/// When answers can be mistaken, hypotheses are never definitively
+/// falsified, but we stop keeping track of those that have become this
+/// improbable.
+pub const NEGLIGIBLE_PROBABILITY: f64 = 1e-9;
+
+/// When answers can be mistaken, a question is never completely
+/// uninformative as long as some hypothesis disagrees with the rest, so we
+/// stop asking once the expected information falls below this many bits.
+pub const NEGLIGIBLE_INFORMATION: f64 = 0.01;
+
+pub fn binary_entropy(p: f64) -> f64 {
+    if p <= 0. || p >= 1. {
+        0.
+    } else {
+        -p * p.log2() - (1. - p) * (1. - p).log2()
+    }
+}
+
+/// The expected reduction in entropy from asking about a study, given the
+/// probability mass of hypotheses predicting that it does or doesn't have the
+/// property: the entropy of the answer, less the entropy it would have even
+/// if we knew the true hypothesis (which is nonzero only if the answer might
+/// be mistaken).
+pub fn information_in_answer(property_mass: f64, negation_mass: f64,
+                             error_rate: f64) -> f64 {
+    if property_mass == 0. || negation_mass == 0. {
+        return 0.;
+    }
+    let p = property_mass / (property_mass + negation_mass);
+    let q = (1. - error_rate) * p + error_rate * (1. - p);
+    binary_entropy(q) - binary_entropy(error_rate)
+}
+
+
+/// What a game is about: what the studies are, how to come up with them,
+/// and which hypotheses about them to entertain.
+pub trait Domain {
+    type Study: Clone;
+    type Hypothesis: Hypothesis<Self::Study> + Ord + Copy;
+
+    fn sample_study<R: Rng>(&self, rng: &mut R) -> Self::Study;
+
+    /// every study, if there are few enough to list
+    fn enumerate_studies(&self) -> Option<Vec<Self::Study>> {
+        None
+    }
+
+    /// the hypotheses, and how probable each is before we've learned
+    /// anything
+    fn prior<R: Rng>(&self, rng: &mut R)
+                     -> Distribution<Self::Hypothesis, Self::Study>;
+
+    /// studies to consider asking about: all of them, if there are few
+    /// enough to list, and otherwise `count` drawn at random
+    fn candidate_studies<R: Rng>(&self, count: usize, rng: &mut R)
+                                 -> Vec<Self::Study> {
+        match self.enumerate_studies() {
+            Some(studies) => studies,
+            None => (0..count).map(|_| self.sample_study(rng)).collect()
+        }
+    }
+}
+
+
+/// beliefs about which of some hypotheses (of type `H`) about studies (of
+/// type `S`) is true
+#[derive(Debug, Clone)]
+pub struct Distribution<H: Ord, S> {
+    backing: BTreeMap<H, f64>,
+    // the probability that any given answer is the opposite of what the true
+    // hypothesis predicts
+    error_rate: f64,
+    study: PhantomData<S>
+}
+
+impl<H: Hypothesis<S> + Ord + Copy, S> Distribution<H, S> {
+    fn from_backing(backing: BTreeMap<H, f64>, error_rate: f64) -> Self {
+        Distribution { backing, error_rate, study: PhantomData }
+    }
+
+    pub fn ignorance_prior(hypotheses: Vec<H>) -> Self {
+        let mut backing = BTreeMap::<H, f64>::new();
+        let probability_each: f64 = 1.0/(hypotheses.len() as f64);
+        for hypothesis in hypotheses.into_iter() {
+            backing.insert(hypothesis, probability_each);
+        }
+        Distribution::from_backing(backing, 0.)
+    }
+
+    /// beliefs in proportion to the given weights
+    pub fn from_weights<I>(weights: I) -> Self
+        where I: IntoIterator<Item=(H, f64)> {
+        let prebacking = BTreeMap::from_iter(weights);
+        let total_mass: f64 = prebacking.values().sum();
+        let normalization_factor = 1.0/total_mass;
+        let backing = prebacking.into_iter()
+            .map(|(h, p)| (h, normalization_factor * p))
+            .collect();
+        Distribution::from_backing(backing, 0.)
+    }
+
+    /// the same beliefs, updated henceforth on the understanding that each
+    /// answer has probability `error_rate` of being mistaken
+    pub fn with_error_rate(self, error_rate: f64) -> Self {
+        Distribution::from_backing(self.backing, error_rate)
+    }
+
+    pub fn error_rate(&self) -> f64 {
+        self.error_rate
+    }
+
+    fn backing(&self) -> &BTreeMap<H, f64> {
+        &self.backing
+    }
+
+    pub fn len(&self) -> usize {
+        self.backing().len()
+    }
+
+    pub fn hypotheses(&self) -> Vec<&H> {
+        self.backing().keys().collect::<Vec<_>>()
+    }
+
+    pub fn belief(&self, hypothesis: H) -> f64 {
+        *self.backing().get(&hypothesis).unwrap_or(&0.0f64)
+    }
+
+    pub fn entropy(&self) -> f64 {
+        self.backing().values().map(|p| -p * p.log2()).sum()
+    }
+
+    pub fn completely_certain(&self) -> Option<H> {
+        if self.backing().len() != 1 {
+            None
+        } else {
+            Some(*self.backing().keys().nth(0).expect("should have one entry"))
+        }
+    }
+
+    /// the most probable hypothesis (the first in order, if there's a tie)
+    /// and its probability
+    pub fn best_guess(&self) -> Option<(H, f64)> {
+        let mut best = None;
+        for (&hypothesis, &probability) in self.backing().iter() {
+            match best {
+                Some((_, top_probability)) if top_probability >= probability => {},
+                _ => { best = Some((hypothesis, probability)); }
+            }
+        }
+        best
+    }
+
+    /// the `n` most probable hypotheses and their probabilities, most
+    /// probable first (and in order, among equals)
+    pub fn most_probable(&self, n: usize) -> Vec<(H, f64)> {
+        let mut ranked = self.backing().iter()
+            .map(|(&hypothesis, &probability)| (hypothesis, probability))
+            .collect::<Vec<_>>();
+        // (a stable sort, so that equals keep their order)
+        ranked.sort_by(|&(_, p), &(_, q)| {
+            q.partial_cmp(&p).expect("probabilities should be comparable")
+        });
+        ranked.truncate(n);
+        ranked
+    }
+
+    /// a hypothesis drawn at random according to our beliefs
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<H> {
+        let mut remaining = rng.gen::<f64>();
+        let mut last = None;
+        for (&hypothesis, &probability) in self.backing().iter() {
+            if remaining < probability {
+                return Some(hypothesis);
+            }
+            remaining -= probability;
+            last = Some(hypothesis);
+        }
+        // (in case rounding left us a hair short of the end)
+        last
+    }
+
+    /// whether a question expected to yield this much information is worth
+    /// asking at all
+    pub fn worth_asking(&self, value_of_information: f64) -> bool {
+        if self.error_rate == 0. {
+            value_of_information > 0.
+        } else {
+            value_of_information > NEGLIGIBLE_INFORMATION
+        }
+    }
+
+    fn likelihood(&self, prediction: bool, verdict: bool) -> f64 {
+        if prediction == verdict {
+            1. - self.error_rate
+        } else {
+            self.error_rate
+        }
+    }
+
+    /// the probability that we'll be told that the study does (`verdict =
+    /// true`) or doesn't have the property
+    pub fn predict(&self, study: &S, verdict: bool) -> f64 {
+        self.predict_by(|h| h.predicts_the_property(study), verdict)
+    }
+
+    /// `predict`, for the `index`th study of a prediction matrix
+    pub fn predict_at(&self, matrix: &PredictionMatrix<H, S>, index: usize,
+                      verdict: bool) -> f64 {
+        self.predict_by(|h| matrix.predicts(h, index), verdict)
+    }
+
+    fn predict_by<F>(&self, prediction: F, verdict: bool) -> f64
+        where F: Fn(&H) -> bool {
+        self.backing().iter()
+            .map(|hp| {
+                let (h, p) = hp;
+                p * self.likelihood(prediction(h), verdict)
+            }).sum()
+    }
+
+    pub fn updated(&self, study: &S, verdict: bool) -> Self {
+        self.updated_by(|h| h.predicts_the_property(study), verdict)
+    }
+
+    /// `updated`, for the `index`th study of a prediction matrix
+    pub fn updated_at(&self, matrix: &PredictionMatrix<H, S>, index: usize,
+                      verdict: bool) -> Self {
+        self.updated_by(|h| matrix.predicts(h, index), verdict)
+    }
+
+    fn updated_by<F>(&self, prediction: F, verdict: bool) -> Self
+        where F: Fn(&H) -> bool {
+        let normalization_factor = 1.0/self.predict_by(&prediction, verdict);
+        let rebacking_pairs = self.backing()
+            .iter().map(|hp| {
+                let (h, p) = hp;
+                let likelihood = self.likelihood(prediction(h), verdict);
+                (*h, normalization_factor * (p * likelihood))
+            }).filter(|hp| {
+                let p = hp.1;
+                p > 0. &&
+                    (self.error_rate == 0. || p > NEGLIGIBLE_PROBABILITY)
+            });
+        let rebacking = BTreeMap::from_iter(rebacking_pairs);
+        Distribution::from_backing(rebacking, self.error_rate)
+    }
+
+    /// how the hypotheses divide over the study
+    pub fn split_on(&self, study: &S) -> Split {
+        let mut split = Split::default();
+        for (hypothesis, &probability) in self.backing().iter() {
+            if hypothesis.predicts_the_property(study) {
+                split.property_mass += probability;
+                split.property_count += 1;
+            } else {
+                split.negation_mass += probability;
+                split.negation_count += 1;
+            }
+        }
+        self.appraised(split)
+    }
+
+    /// how the hypotheses divide over each of the studies of a prediction
+    /// matrix (the same as `split_on` each, but tallied by popcount)
+    pub fn splits_over(&self, matrix: &PredictionMatrix<H, S>) -> Vec<Split>
+        where H: Sync, S: Sync {
+        let classes = matrix.classes(|hypothesis| self.belief(hypothesis));
+        let indices = (0..matrix.studies().len()).collect::<Vec<_>>();
+        parallel_map(&indices, |&index| {
+            self.split_among(&classes, matrix.predictions(index))
+        })
+    }
+
+    /// how classes of equally probable hypotheses divide over a study,
+    /// given which of them predict that it has the property
+    fn split_among(&self, classes: &[(f64, Bitset)], predictions: &Bitset)
+                   -> Split {
+        let mut split = Split::default();
+        for &(probability, ref mask) in classes {
+            let agreeing = predictions.count_ones_within(mask);
+            let disagreeing = mask.count_ones() - agreeing;
+            split.property_mass += probability * agreeing as f64;
+            split.property_count += agreeing;
+            split.negation_mass += probability * disagreeing as f64;
+            split.negation_count += disagreeing;
+        }
+        self.appraised(split)
+    }
+
+    /// the split, with how much asking about it would tell us
+    fn appraised(&self, mut split: Split) -> Split {
+        split.value_of_information = information_in_answer(
+            split.property_mass, split.negation_mass, self.error_rate);
+        split.worth_asking = self.worth_asking(split.value_of_information);
+        split
+    }
+
+    pub fn value_of_information(&self, study: &S) -> f64 {
+        self.split_on(study).value_of_information
+    }
+
+    /// the study that a strategy would rather ask about
+    pub fn burning_question_by(&self, studies: &[S], strategy: &dyn Strategy,
+                               rng: &mut dyn Rng) -> Option<S>
+        where S: Clone {
+        let splits = studies.iter()
+            .map(|study| self.split_on(study))
+            .collect::<Vec<_>>();
+        strategy.choose(&splits, rng).map(|index| studies[index].clone())
+    }
+
+    /// the index of the study of a prediction matrix that a strategy would
+    /// rather ask about
+    pub fn burning_question_in(&self, matrix: &PredictionMatrix<H, S>,
+                               strategy: &dyn Strategy, rng: &mut dyn Rng)
+                               -> Option<usize>
+        where H: Sync, S: Sync {
+        strategy.choose(&self.splits_over(matrix), rng)
+    }
+
+    /// The index of the study of a prediction matrix with the greatest
+    /// expected information over two questions: its own, plus that of the
+    /// best question to ask after hearing its answer. (A greedy choice can
+    /// leave us with a split that no remaining question divides evenly.)
+    /// Falls back to the greedy choice if there's no time to score
+    /// anything.
+    pub fn burning_question_looking_ahead(&self,
+                                          matrix: &PredictionMatrix<H, S>,
+                                          lookahead: &Lookahead)
+                                          -> Option<usize>
+        where H: Send + Sync, S: Sync {
+        let start = Instant::now();
+        let mut ranked = self.splits_over(matrix).into_iter()
+            .map(|split| split.value_of_information)
+            .enumerate()
+            .collect::<Vec<_>>();
+        ranked.sort_by(|&(i, v), &(j, w)| {
+            w.partial_cmp(&v).expect("values should be comparable")
+                .then(i.cmp(&j))
+        });
+        ranked.truncate(cmp::max(lookahead.candidates, 1));
+        let candidates = ranked.iter()
+            .map(|&(index, _)| index)
+            .collect::<Vec<_>>();
+
+        let scores = parallel_map(&ranked, |&(index, value)| {
+            if start.elapsed() > lookahead.time_budget {
+                return None;
+            }
+            let mut expected_followup = 0.;
+            for &verdict in &[true, false] {
+                let probability = self.predict_at(matrix, index, verdict);
+                if probability == 0. {
+                    continue;
+                }
+                let after = self.updated_at(matrix, index, verdict);
+                let classes = matrix.classes(
+                    |hypothesis| after.belief(hypothesis));
+                let best_followup = candidates.iter()
+                    .map(|&followup| {
+                        after.split_among(&classes,
+                                          matrix.predictions(followup))
+                            .value_of_information
+                    })
+                    .fold(0., f64::max);
+                expected_followup += probability * best_followup;
+            }
+            Some(value + expected_followup)
+        });
+
+        let mut best_index = candidates.first().cloned();
+        let mut top_score = f64::NEG_INFINITY;
+        for (&index, score) in candidates.iter().zip(scores) {
+            if let Some(score) = score {
+                // (ties, up to rounding, go to the better question on its
+                // own)
+                if score > top_score + 1e-12 {
+                    top_score = score;
+                    best_index = Some(index);
+                }
+            }
+        }
+        best_index
+    }
+
+    pub fn inspect(&self, n: usize) {
+        let mut backing = self.backing().iter().collect::<Vec<_>>();
+        backing.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(Ordering::Equal));
+        let total_probability_mass: f64 = backing.iter()
+            .map(|hp| { hp.1 }).sum();
+        println!("Total probability mass: {:.6}", total_probability_mass);
+        println!("Top {} hypotheses:", n);
+        for &(&hypothesis, &probability) in backing.iter().take(n) {
+            wrapln!(" * {}: {}", hypothesis.description(),
+                    Style::new().bold().paint(&format!("{:.4}", probability)));
+        }
+    }
+}
@@ -0,0 +1,435 @@
+use std::fmt;
+
+use rand::Rng;
+
+use inference::distribution;
+use inference::distribution::Domain;
+use inference::hypothesis;
+use inference::hypothesis::joined_space;
+pub use inference::hypothesis::Hypothesis;
+use inference::number::{BoundednessHypothesis, DivisibilityHypothesis};
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct Point {
+    pub x: u16,
+    pub y: u16
+}
+
+impl Point {
+    pub fn new(x: u16, y: u16) -> Self {
+        Point { x, y }
+    }
+
+    pub fn coordinate(&self, axis: Axis) -> u16 {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y
+        }
+    }
+
+    /// the square of the distance from the origin (so as to stay within
+    /// the integers, and wide enough not to overflow whatever the
+    /// coordinates)
+    pub fn squared_distance(&self) -> u32 {
+        let (x, y) = (self.x as u32, self.y as u32);
+        x * x + y * y
+    }
+
+    /// The point in compact notation: its coordinates separated by a comma
+    /// (e.g., `3,4`).
+    pub fn to_notation(self) -> String {
+        format!("{},{}", self.x, self.y)
+    }
+
+    pub fn from_notation(notation: &str) -> Result<Self, String> {
+        let coordinates = notation.split(',')
+            .map(|coordinate| coordinate.trim().parse::<u16>())
+            .collect::<Vec<_>>();
+        match &coordinates[..] {
+            &[Ok(x), Ok(y)] => Ok(Point::new(x, y)),
+            _ => Err(format!("expected two coordinates in {:?}", notation))
+        }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+
+pub type Study = Point;
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum Axis {
+    X,
+    Y
+}
+
+pub static AXES: [Axis; 2] = [Axis::X, Axis::Y];
+
+impl fmt::Display for Axis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Axis::X => write!(f, "x"),
+            Axis::Y => write!(f, "y")
+        }
+    }
+}
+
+
+/// the number game's bounds, describing one coordinate
+fn describe_bounds(name: &str, bounds: &BoundednessHypothesis) -> String {
+    match (bounds.lower, bounds.upper) {
+        (Some(min), Some(max)) if min == max => {
+            format!("{} is {}", name, min)
+        },
+        (Some(min), Some(max)) => {
+            format!("{} is between {} and {}", name, min, max)
+        },
+        (Some(min), None) => format!("{} is at least {}", name, min),
+        (None, Some(max)) => format!("{} is at most {}", name, max),
+        (None, None) => format!("{} is anything", name)
+    }
+}
+
+
+/// the points on one side of a vertical or horizontal line
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct HalfPlaneHypothesis {
+    pub axis: Axis,
+    pub bounds: BoundednessHypothesis
+}
+
+impl HalfPlaneHypothesis {
+    pub fn new_lower(axis: Axis, lower: u16) -> Self {
+        HalfPlaneHypothesis {
+            axis, bounds: BoundednessHypothesis::new_lower(lower) }
+    }
+
+    pub fn new_upper(axis: Axis, upper: u16) -> Self {
+        HalfPlaneHypothesis {
+            axis, bounds: BoundednessHypothesis::new_upper(upper) }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::HalfPlane(self)
+    }
+}
+
+impl Hypothesis<Study> for HalfPlaneHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        self.bounds.predicts_the_property(&study.coordinate(self.axis))
+    }
+
+    fn description(&self) -> String {
+        describe_bounds(&self.axis.to_string(), &self.bounds)
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct RectangleHypothesis {
+    pub x: BoundednessHypothesis,
+    pub y: BoundednessHypothesis
+}
+
+impl RectangleHypothesis {
+    pub fn new(x_min: u16, x_max: u16, y_min: u16, y_max: u16) -> Self {
+        RectangleHypothesis { x: BoundednessHypothesis::new(x_min, x_max),
+                              y: BoundednessHypothesis::new(y_min, y_max) }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Rectangle(self)
+    }
+}
+
+impl Hypothesis<Study> for RectangleHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        self.x.predicts_the_property(&study.x) &&
+            self.y.predicts_the_property(&study.y)
+    }
+
+    fn description(&self) -> String {
+        format!("it lies in the rectangle where {} and {}",
+                describe_bounds("x", &self.x), describe_bounds("y", &self.y))
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct DiagonalHypothesis {
+    /// whether the diagonal is the one running from the top left to the
+    /// bottom right (rather than through the origin)
+    pub anti: bool,
+    /// the largest coordinate on the grid
+    pub extent: u16
+}
+
+impl DiagonalHypothesis {
+    pub fn new(anti: bool, extent: u16) -> Self {
+        DiagonalHypothesis { anti, extent }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Diagonal(self)
+    }
+}
+
+impl Hypothesis<Study> for DiagonalHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        if self.anti {
+            study.x + study.y == self.extent
+        } else {
+            study.x == study.y
+        }
+    }
+
+    fn description(&self) -> String {
+        if self.anti {
+            format!("it lies on the diagonal where x + y = {}", self.extent)
+        } else {
+            "it lies on the diagonal where x = y".to_owned()
+        }
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct ParityHypothesis {
+    pub even: bool
+}
+
+impl ParityHypothesis {
+    pub fn new(even: bool) -> Self {
+        ParityHypothesis { even }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Parity(self)
+    }
+}
+
+impl Hypothesis<Study> for ParityHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        DivisibilityHypothesis::new(2)
+            .predicts_the_property(&(study.x + study.y)) == self.even
+    }
+
+    fn description(&self) -> String {
+        format!("x + y is {}", if self.even { "even" } else { "odd" })
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct DistanceHypothesis {
+    pub radius: u16,
+    // (whether points at most `radius` from the origin have the property,
+    // rather than those at least `radius` from it)
+    pub within: bool
+}
+
+impl DistanceHypothesis {
+    pub fn new_within(radius: u16) -> Self {
+        DistanceHypothesis { radius, within: true }
+    }
+
+    pub fn new_beyond(radius: u16) -> Self {
+        DistanceHypothesis { radius, within: false }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Distance(self)
+    }
+}
+
+impl Hypothesis<Study> for DistanceHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let squared_radius = self.radius as u32 * self.radius as u32;
+        if self.within {
+            study.squared_distance() <= squared_radius
+        } else {
+            study.squared_distance() >= squared_radius
+        }
+    }
+
+    fn description(&self) -> String {
+        if self.within {
+            format!("it is within {} of the origin", self.radius)
+        } else {
+            format!("it is at least {} from the origin", self.radius)
+        }
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum BasicHypothesis {
+    HalfPlane(HalfPlaneHypothesis),
+    Rectangle(RectangleHypothesis),
+    Diagonal(DiagonalHypothesis),
+    Parity(ParityHypothesis),
+    Distance(DistanceHypothesis)
+}
+
+impl Hypothesis<Study> for BasicHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        match *self {
+            BasicHypothesis::HalfPlane(h) => h.predicts_the_property(study),
+            BasicHypothesis::Rectangle(h) => h.predicts_the_property(study),
+            BasicHypothesis::Diagonal(h) => h.predicts_the_property(study),
+            BasicHypothesis::Parity(h) => h.predicts_the_property(study),
+            BasicHypothesis::Distance(h) => h.predicts_the_property(study)
+        }
+    }
+    fn description(&self) -> String {
+        match *self {
+            BasicHypothesis::HalfPlane(h) => h.description(),
+            BasicHypothesis::Rectangle(h) => h.description(),
+            BasicHypothesis::Diagonal(h) => h.description(),
+            BasicHypothesis::Parity(h) => h.description(),
+            BasicHypothesis::Distance(h) => h.description()
+        }
+    }
+}
+
+
+/// a basic criterion for points, or the conjunction or disjunction of two
+pub type JoinedHypothesis = hypothesis::JoinedHypothesis<BasicHypothesis>;
+
+pub type Distribution<H> = distribution::Distribution<H, Study>;
+
+
+/// every point with coordinates less than `size`
+pub fn points(size: u16) -> Vec<Point> {
+    (0..size)
+        .flat_map(|y| (0..size).map(move |x| Point::new(x, y)))
+        .collect()
+}
+
+/// half-planes, diagonals, parity, and distance from the origin, for a grid
+/// of this size
+pub fn standard_basic_hypotheses(size: u16) -> Vec<BasicHypothesis> {
+    let extent = size - 1;
+    let mut basics = Vec::new();
+    for &axis in &AXES {
+        for bound in 1..size {
+            basics.push(HalfPlaneHypothesis::new_lower(axis, bound)
+                        .to_basic());
+        }
+        for bound in 0..extent {
+            basics.push(HalfPlaneHypothesis::new_upper(axis, bound)
+                        .to_basic());
+        }
+    }
+    for &anti in &[false, true] {
+        basics.push(DiagonalHypothesis::new(anti, extent).to_basic());
+    }
+    for &even in &[true, false] {
+        basics.push(ParityHypothesis::new(even).to_basic());
+    }
+    for radius in 1..size + 1 {
+        basics.push(DistanceHypothesis::new_within(radius).to_basic());
+        basics.push(DistanceHypothesis::new_beyond(radius).to_basic());
+    }
+    basics
+}
+
+/// the standard criteria and their conjunctions and disjunctions, along with
+/// every rectangle (which, being conjunctions already, aren't joined
+/// further)—omitting any that would accept every point or none, or that
+/// accepts just the same points as one already included
+pub fn hypothesis_space(size: u16) -> Vec<JoinedHypothesis> {
+    let mut rectangles = Vec::new();
+    for x_min in 0..size {
+        for x_max in x_min..size {
+            for y_min in 0..size {
+                for y_max in y_min..size {
+                    rectangles.push(JoinedHypothesis::full_stop(
+                        RectangleHypothesis::new(x_min, x_max, y_min, y_max)
+                            .to_basic()));
+                }
+            }
+        }
+    }
+    joined_space(&standard_basic_hypotheses(size), rectangles, &points(size))
+}
+
+
+/// points on a square grid, `size` to a side
+pub struct GridDomain {
+    pub size: u16
+}
+
+impl Domain for GridDomain {
+    type Study = Study;
+    type Hypothesis = JoinedHypothesis;
+
+    fn sample_study<R: Rng>(&self, rng: &mut R) -> Study {
+        let x = rng.gen_range(0, self.size);
+        Point::new(x, rng.gen_range(0, self.size))
+    }
+
+    fn enumerate_studies(&self) -> Option<Vec<Study>> {
+        Some(points(self.size))
+    }
+
+    fn prior<R: Rng>(&self, _rng: &mut R) -> Distribution<JoinedHypothesis> {
+        Distribution::ignorance_prior(hypothesis_space(self.size))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concerning_criteria_for_points() {
+        let point = Point::new(3, 4);
+        assert_eq!(Ok(point), Point::from_notation(" 3, 4"));
+        assert!(Point::from_notation("3").is_err());
+        assert!(HalfPlaneHypothesis::new_lower(Axis::Y, 4)
+                .predicts_the_property(&point));
+        assert!(!HalfPlaneHypothesis::new_upper(Axis::X, 2)
+                .predicts_the_property(&point));
+        assert!(RectangleHypothesis::new(2, 3, 0, 7)
+                .predicts_the_property(&point));
+        assert!(DiagonalHypothesis::new(true, 7)
+                .predicts_the_property(&point));
+        assert!(ParityHypothesis::new(false).predicts_the_property(&point));
+        assert!(DistanceHypothesis::new_within(5)
+                .predicts_the_property(&point));
+        assert!(!DistanceHypothesis::new_beyond(6)
+                .predicts_the_property(&point));
+        // (squared distances this far out don't fit in a u16)
+        let far_point = Point::new(200, 200);
+        assert_eq!(80000, far_point.squared_distance());
+        assert!(DistanceHypothesis::new_within(283)
+                .predicts_the_property(&far_point));
+        assert!(!DistanceHypothesis::new_beyond(283)
+                .predicts_the_property(&far_point));
+        assert_eq!("it lies in the rectangle where x is between 2 and 3 and \
+                    y is 4",
+                   RectangleHypothesis::new(2, 3, 4, 4).description());
+    }
+
+    #[test]
+    fn concerning_the_hypothesis_space() {
+        let hypotheses = hypothesis_space(4);
+        // (a rectangle spanning the grid along one axis is just a
+        // half-plane)
+        assert!(!hypotheses.contains(&JoinedHypothesis::full_stop(
+            RectangleHypothesis::new(0, 3, 2, 3).to_basic())));
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(
+            HalfPlaneHypothesis::new_lower(Axis::Y, 2).to_basic())));
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(
+            RectangleHypothesis::new(1, 2, 1, 2).to_basic())));
+    }
+
+}
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+
+use inference::distribution::Domain;
+
+
+/// a criterion for which studies (of type `S`) have the property
+pub trait Hypothesis<S> {
+    fn predicts_the_property(&self, study: &S) -> bool;
+    fn description(&self) -> String;
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum Remainder<B> {
+    And(B),
+    Or(B),
+    FullStop
+}
+
+
+/// a basic criterion, or the conjunction or disjunction of two
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct JoinedHypothesis<B> {
+    pub proposition: B,
+    pub remainder: Remainder<B>
+}
+
+impl<B: Copy> JoinedHypothesis<B> {
+    pub fn full_stop(hypothesis: B) -> Self {
+        JoinedHypothesis { proposition: hypothesis,
+                           remainder: Remainder::FullStop }
+    }
+
+    pub fn and(first_conjunct: B, second_conjunct: B) -> Self {
+        JoinedHypothesis { proposition: first_conjunct,
+                           remainder: Remainder::And(second_conjunct) }
+    }
+
+    pub fn or(first_disjunct: B, second_disjunct: B) -> Self {
+        JoinedHypothesis { proposition: first_disjunct,
+                           remainder: Remainder::Or(second_disjunct) }
+    }
+
+    /// the basic criteria this joins (one or two of them)
+    pub fn basics(&self) -> Vec<B> {
+        match self.remainder {
+            Remainder::And(other) | Remainder::Or(other) => {
+                vec![self.proposition, other]
+            },
+            Remainder::FullStop => vec![self.proposition]
+        }
+    }
+
+    /// whether random studies from the domain turn up both examples and
+    /// counterexamples (within `sample_cap` tries)
+    pub fn check_substantiality<D: Domain, R: Rng>(&self, domain: &D,
+                                                   sample_cap: usize,
+                                                   rng: &mut R) -> bool
+        where B: Hypothesis<D::Study> {
+        let mut falsifiable = false;
+        let mut confirmable = false;
+        for _ in 0..sample_cap {
+            let study = domain.sample_study(rng);
+            if self.predicts_the_property(&study) {
+                confirmable = true;
+            } else {
+                falsifiable = true;
+            }
+            if falsifiable && confirmable {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+
+impl<S, B: Hypothesis<S>> Hypothesis<S> for JoinedHypothesis<B> {
+    fn predicts_the_property(&self, study: &S) -> bool {
+        match self.remainder {
+            Remainder::And(ref conjunct) => {
+                self.proposition.predicts_the_property(study) &&
+                    conjunct.predicts_the_property(study)
+            },
+            Remainder::Or(ref disjunct) => {
+                self.proposition.predicts_the_property(study) ||
+                    disjunct.predicts_the_property(study)
+            },
+            Remainder::FullStop => {
+                self.proposition.predicts_the_property(study)
+            }
+        }
+    }
+
+    fn description(&self) -> String {
+        match self.remainder {
+            Remainder::And(ref conjunct) => {
+                format!("{} and {}", self.proposition.description(),
+                        conjunct.description())
+            },
+            Remainder::Or(ref disjunct) => {
+                format!("{} or {}", self.proposition.description(),
+                        disjunct.description())
+            },
+            Remainder::FullStop => {
+                self.proposition.description()
+            }
+        }
+    }
+}
+
+
+/// The basic criteria on their own, then `extras` (any other hypotheses the
+/// domain entertains), then the conjunction and disjunction of every pair of
+/// criteria—omitting any that would predict the property of every one of
+/// the studies or none, or that predicts it of just the same studies as one
+/// already included.
+pub fn joined_space<B, S>(basics: &[B], extras: Vec<JoinedHypothesis<B>>,
+                          studies: &[S]) -> Vec<JoinedHypothesis<B>>
+    where B: Hypothesis<S> + Copy {
+    let mut candidates = basics.iter()
+        .map(|&basic| JoinedHypothesis::full_stop(basic))
+        .collect::<Vec<_>>();
+    candidates.extend(extras);
+    for (i, &first) in basics.iter().enumerate() {
+        for &second in &basics[i+1..] {
+            candidates.push(JoinedHypothesis::and(first, second));
+            candidates.push(JoinedHypothesis::or(first, second));
+        }
+    }
+
+    let mut hypotheses = Vec::new();
+    let mut predictions = HashSet::new();
+    for candidate in candidates {
+        let prediction = studies.iter()
+            .map(|study| candidate.predicts_the_property(study))
+            .collect::<Vec<_>>();
+        let substantial = prediction.iter().any(|&p| p) &&
+            prediction.iter().any(|&p| !p);
+        if substantial && !predictions.contains(&prediction) {
+            hypotheses.push(candidate);
+            predictions.insert(prediction);
+        }
+    }
+    hypotheses
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inference::number::DivisibilityHypothesis;
+
+    #[test]
+    fn concerning_the_joined_space() {
+        let even = DivisibilityHypothesis::new(2);
+        let by_four = DivisibilityHypothesis::new(4);
+        let everything = JoinedHypothesis::full_stop(
+            DivisibilityHypothesis::new(1));
+        let studies = (1..9).collect::<Vec<u16>>();
+        // (the conjunction accepts just the multiples of four, and the
+        // disjunction just the even numbers, and every number is divisible
+        // by one)
+        assert_eq!(vec![JoinedHypothesis::full_stop(even),
+                        JoinedHypothesis::full_stop(by_four)],
+                   joined_space(&[even, by_four], vec![everything],
+                                &studies));
+    }
+
+}
@@ -1,2 +1,11 @@
+pub mod bitset;
+pub mod card;
+pub mod distribution;
+pub mod grid;
+pub mod hypothesis;
 pub mod number;
+pub mod parallel;
+pub mod prediction_matrix;
+pub mod strategy;
 pub mod triangle;
+pub mod word;
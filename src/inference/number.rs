@@ -1,22 +1,20 @@
 #![allow(dead_code)]
 
-use std::collections::HashMap;
-use std::process::Command;
-use std::hash::Hash;
-use std::cmp::Eq;
-use std::iter::FromIterator;
-use std::f64::NEG_INFINITY;
+use std::collections::HashSet;
+use std::sync::OnceLock;
 
+use rand::Rng;
 
-pub type Study = u16;
+use inference::distribution;
+use inference::distribution::Domain;
+use inference::hypothesis;
+pub use inference::hypothesis::Hypothesis;
 
-pub trait Hypothesis {
-    fn predicts_the_property(&self, study: Study) -> bool;
-    fn description(&self) -> String;
-}
 
+pub type Study = u16;
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
 pub struct DivisibilityHypothesis {
     pub n: u16
 }
@@ -31,9 +29,9 @@ impl DivisibilityHypothesis {
     }
 }
 
-impl Hypothesis for DivisibilityHypothesis {
-    fn predicts_the_property(&self, study: Study) -> bool {
-        study % self.n == 0
+impl Hypothesis<Study> for DivisibilityHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        study.is_multiple_of(self.n)
     }
 
     fn description(&self) -> String {
@@ -42,7 +40,7 @@ impl Hypothesis for DivisibilityHypothesis {
 }
 
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
 pub struct BoundednessHypothesis {
     pub lower: Option<u16>,
     pub upper: Option<u16>
@@ -63,15 +61,15 @@ impl BoundednessHypothesis {
     }
 }
 
-impl Hypothesis for BoundednessHypothesis {
-    fn predicts_the_property(&self, study: Study) -> bool {
+impl Hypothesis<Study> for BoundednessHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
         if let Some(min) = self.lower {
-            if study < min {
+            if *study < min {
                 return false;
             }
         }
         if let Some(max) = self.upper {
-            if study > max {
+            if *study > max {
                 return false;
             }
         }
@@ -94,223 +92,799 @@ impl Hypothesis for BoundednessHypothesis {
 }
 
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
-pub enum BasicHypothesis {
-    Divisibility(DivisibilityHypothesis),
-    Boundedness(BoundednessHypothesis)
+/// the smallest prime factor of every number up to some limit, from which
+/// to factorize any of them without trial division
+pub struct Sieve {
+    smallest_prime_factors: Vec<u16>
 }
 
-impl Hypothesis for BasicHypothesis {
-    fn predicts_the_property(&self, study: Study) -> bool {
-        match *self {
-            BasicHypothesis::Divisibility(h) => h.predicts_the_property(study),
-            BasicHypothesis::Boundedness(h) => h.predicts_the_property(study)
+impl Sieve {
+    pub fn new(limit: u16) -> Self {
+        let limit = limit as usize;
+        let mut smallest_prime_factors = vec![0u16; limit + 1];
+        for n in 2..limit + 1 {
+            if smallest_prime_factors[n] != 0 {
+                continue;
+            }
+            // `n` is prime, and the smallest prime factor of any multiple
+            // not already claimed by a smaller prime
+            let mut multiple = n;
+            while multiple <= limit {
+                if smallest_prime_factors[multiple] == 0 {
+                    smallest_prime_factors[multiple] = n as u16;
+                }
+                multiple += n;
+            }
         }
+        Sieve { smallest_prime_factors }
     }
-    fn description(&self) -> String {
-        match *self {
-            BasicHypothesis::Divisibility(h) => h.description(),
-            BasicHypothesis::Boundedness(h) => h.description()
+
+    pub fn limit(&self) -> u16 {
+        (self.smallest_prime_factors.len() - 1) as u16
+    }
+
+    pub fn is_prime(&self, n: u16) -> bool {
+        n >= 2 && self.smallest_prime_factors[n as usize] == n
+    }
+
+    /// the prime factors of `n` in ascending order, repeated according to
+    /// their multiplicity (none, for 1; nor for 0, which has no
+    /// factorization)
+    pub fn prime_factors(&self, n: u16) -> Vec<u16> {
+        assert!(n <= self.limit(),
+                "{} is outside the sieve (0 to {})", n, self.limit());
+        let mut factors = Vec::new();
+        let mut rest = n;
+        while rest > 1 {
+            let factor = self.smallest_prime_factors[rest as usize];
+            factors.push(factor);
+            rest /= factor;
+        }
+        factors
+    }
+
+    /// each distinct prime factor of `n`, and how many times it divides `n`
+    fn prime_powers(&self, n: u16) -> Vec<(u16, u32)> {
+        let mut powers: Vec<(u16, u32)> = Vec::new();
+        for factor in self.prime_factors(n) {
+            match powers.last_mut() {
+                Some(&mut (prime, ref mut exponent)) if prime == factor => {
+                    *exponent += 1;
+                    continue;
+                },
+                _ => {}
+            }
+            powers.push((factor, 1));
+        }
+        powers
+    }
+
+    /// every divisor of `n` (including 1 and `n` itself), in ascending order
+    /// (`n` should be positive: every number divides 0)
+    pub fn divisors(&self, n: u16) -> Vec<u16> {
+        let mut divisors = vec![1u16];
+        for (prime, exponent) in self.prime_powers(n) {
+            let mut extended = Vec::new();
+            for &divisor in &divisors {
+                let mut multiple = divisor;
+                for _ in 0..exponent + 1 {
+                    extended.push(multiple);
+                    multiple = multiple.saturating_mul(prime);
+                }
+            }
+            divisors = extended;
         }
+        divisors.sort();
+        divisors
     }
+
+    pub fn divisor_count(&self, n: u16) -> u16 {
+        self.prime_powers(n).iter()
+            .map(|&(_, exponent)| exponent as u16 + 1)
+            .product()
+    }
+}
+
+/// a sieve covering every number the game could ask about
+pub fn sieve() -> &'static Sieve {
+    static SIEVE: OnceLock<Sieve> = OnceLock::new();
+    SIEVE.get_or_init(|| Sieve::new(Study::MAX))
 }
 
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
-pub enum Remainder {
-    And(BasicHypothesis),
-    Or(BasicHypothesis),
-    FullStop
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct PrimeHypothesis;
+
+impl PrimeHypothesis {
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Prime(self)
+    }
 }
 
+impl Hypothesis<Study> for PrimeHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        sieve().is_prime(*study)
+    }
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
-pub struct JoinedHypothesis {
-    pub proposition: BasicHypothesis,
-    pub remainder: Remainder
+    fn description(&self) -> String {
+        "it is prime".to_owned()
+    }
+}
+
+
+/// the number is some whole number raised to `exponent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct PerfectPowerHypothesis {
+    pub exponent: u32
 }
 
-impl JoinedHypothesis {
-    pub fn full_stop(hypothesis: BasicHypothesis) -> Self {
-        JoinedHypothesis { proposition: hypothesis,
-                           remainder: Remainder::FullStop }
+impl PerfectPowerHypothesis {
+    pub fn new(exponent: u32) -> Self {
+        PerfectPowerHypothesis { exponent }
     }
 
-    pub fn and(first_conjunct: BasicHypothesis,
-               second_conjunct: BasicHypothesis) -> Self {
-        JoinedHypothesis { proposition: first_conjunct,
-                           remainder: Remainder::And(second_conjunct) }
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::PerfectPower(self)
     }
+}
 
-    pub fn or(first_disjunct: BasicHypothesis,
-              second_disjunct: BasicHypothesis) -> Self {
-        JoinedHypothesis { proposition: first_disjunct,
-                           remainder: Remainder::Or(second_disjunct) }
+impl Hypothesis<Study> for PerfectPowerHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let study = *study as u32;
+        (1..).map(|root: u32| root.pow(self.exponent))
+            .take_while(|&power| power <= study)
+            .any(|power| power == study)
+    }
+
+    fn description(&self) -> String {
+        match self.exponent {
+            2 => "it is a perfect square".to_owned(),
+            3 => "it is a perfect cube".to_owned(),
+            exponent => format!("it is a perfect {}th power", exponent)
+        }
     }
 }
 
-impl Hypothesis for JoinedHypothesis {
-    // EXCITING TODO: these two methods have a pretty similar structure—time
-    // for a macro?!
 
-    fn predicts_the_property(&self, study: Study) -> bool {
-        match self.remainder {
-            Remainder::And(ref conjunct) => {
-                self.proposition.predicts_the_property(study) &&
-                    conjunct.predicts_the_property(study)
-            },
-            Remainder::Or(ref disjunct) => {
-                self.proposition.predicts_the_property(study) ||
-                    disjunct.predicts_the_property(study)
-            },
-            Remainder::FullStop => {
-                self.proposition.predicts_the_property(study)
-            }
+/// the number is `base` raised to some whole number (including zero)
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct PowerHypothesis {
+    pub base: u16
+}
+
+impl PowerHypothesis {
+    pub fn new(base: u16) -> Self {
+        PowerHypothesis { base }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Power(self)
+    }
+}
+
+impl Hypothesis<Study> for PowerHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let mut power = 1u32;
+        while power < *study as u32 {
+            power *= self.base as u32;
         }
+        power == *study as u32
     }
 
     fn description(&self) -> String {
-        match self.remainder {
-            Remainder::And(ref conjunct) => {
-                format!("{} and {}", self.proposition.description(),
-                        conjunct.description())
-            },
-            Remainder::Or(ref disjunct) => {
-                format!("{} or {}", self.proposition.description(),
-                        disjunct.description())
-            },
-            Remainder::FullStop => {
-                self.proposition.description()
-            }
+        format!("it is a power of {}", self.base)
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct TriangularHypothesis;
+
+impl TriangularHypothesis {
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Triangular(self)
+    }
+}
+
+impl Hypothesis<Study> for TriangularHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let study = *study as u32;
+        (1..).map(|n: u32| n * (n + 1) / 2)
+            .take_while(|&triangular| triangular <= study)
+            .any(|triangular| triangular == study)
+    }
+
+    fn description(&self) -> String {
+        "it is a triangular number".to_owned()
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct FibonacciHypothesis;
+
+impl FibonacciHypothesis {
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Fibonacci(self)
+    }
+}
+
+impl Hypothesis<Study> for FibonacciHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let study = *study as u32;
+        let (mut previous, mut current) = (0u32, 1u32);
+        while current < study {
+            let next = previous + current;
+            previous = current;
+            current = next;
         }
+        current == study
+    }
+
+    fn description(&self) -> String {
+        "it is a Fibonacci number".to_owned()
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct DivisorCountHypothesis {
+    pub count: u16
+}
+
+impl DivisorCountHypothesis {
+    pub fn new(count: u16) -> Self {
+        DivisorCountHypothesis { count }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::DivisorCount(self)
     }
 }
 
+impl Hypothesis<Study> for DivisorCountHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        // (0 has more divisors than any count)
+        *study != 0 && sieve().divisor_count(*study) == self.count
+    }
+
+    fn description(&self) -> String {
+        format!("it has exactly {} divisor{}",
+                self.count, if self.count == 1 { "" } else { "s" })
+    }
+}
 
-pub struct Distribution<H: Hypothesis + Hash + Eq>(HashMap<H, f64>);
 
-impl<H: Hypothesis + Hash + Eq + Copy> Distribution<H> {
-    pub fn new() -> Self {
-        let backing = HashMap::<H, f64>::new();
-        Distribution(backing)
+/// the number has exactly `count` prime factors, counting each as often as
+/// it divides the number (or, if `distinct`, just once)
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct PrimeFactorCountHypothesis {
+    pub count: u16,
+    pub distinct: bool
+}
+
+impl PrimeFactorCountHypothesis {
+    pub fn new(count: u16, distinct: bool) -> Self {
+        PrimeFactorCountHypothesis { count, distinct }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::PrimeFactorCount(self)
     }
+}
 
-    pub fn ignorance_prior(hypotheses: Vec<H>) -> Self {
-        let mut backing = HashMap::<H, f64>::new();
-        let probability_each: f64 = 1.0/(hypotheses.len() as f64);
-        for hypothesis in hypotheses.into_iter() {
-            backing.insert(hypothesis, probability_each);
+impl Hypothesis<Study> for PrimeFactorCountHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let mut factors = sieve().prime_factors(*study);
+        if self.distinct {
+            factors.dedup();
         }
-        Distribution(backing)
+        factors.len() == self.count as usize
+    }
+
+    fn description(&self) -> String {
+        format!("it has exactly {} {}prime factor{}", self.count,
+                if self.distinct { "distinct " } else { "" },
+                if self.count == 1 { "" } else { "s" })
+    }
+}
+
+
+/// no prime divides the number more than once
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct SquarefreeHypothesis;
+
+impl SquarefreeHypothesis {
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Squarefree(self)
+    }
+}
+
+impl Hypothesis<Study> for SquarefreeHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        // (0 is divisible by every square)
+        let factors = sieve().prime_factors(*study);
+        *study != 0 && factors.windows(2).all(|pair| pair[0] != pair[1])
+    }
+
+    fn description(&self) -> String {
+        "it is squarefree".to_owned()
+    }
+}
+
+
+/// the number leaves a (non-zero) `remainder` when divided by `modulus`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct ResidueHypothesis {
+    pub modulus: u16,
+    pub remainder: u16
+}
+
+impl ResidueHypothesis {
+    pub fn new(modulus: u16, remainder: u16) -> Self {
+        ResidueHypothesis { modulus, remainder }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Residue(self)
+    }
+}
+
+impl Hypothesis<Study> for ResidueHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        study % self.modulus == self.remainder
+    }
+
+    fn description(&self) -> String {
+        format!("it is congruent to {} modulo {}",
+                self.remainder, self.modulus)
+    }
+}
+
+
+/// the number's digits in this base, most significant first
+fn digits(n: u16, base: u16) -> Vec<u16> {
+    let mut digits = Vec::new();
+    let mut rest = n;
+    while rest > 0 {
+        digits.push(rest % base);
+        rest /= base;
+    }
+    digits.reverse();
+    digits
+}
+
+/// how to say which base digit-based criteria are about (base ten going
+/// without saying)
+fn in_base(base: u16) -> String {
+    match base {
+        10 => String::new(),
+        2 => " in binary".to_owned(),
+        base => format!(" in base {}", base)
     }
+}
 
-    fn backing(&self) -> &HashMap<H, f64> {
-        &self.0
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct ContainsDigitHypothesis {
+    pub digit: u16,
+    pub base: u16
+}
+
+impl ContainsDigitHypothesis {
+    pub fn new(digit: u16, base: u16) -> Self {
+        ContainsDigitHypothesis { digit, base }
     }
 
-    fn mut_backing(&mut self) -> &mut HashMap<H, f64> {
-        &mut self.0
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::ContainsDigit(self)
     }
+}
 
-    pub fn len(&self) -> usize {
-        self.backing().len()
+impl Hypothesis<Study> for ContainsDigitHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        digits(*study, self.base).contains(&self.digit)
     }
 
-    pub fn hypotheses(&self) -> Vec<&H> {
-        self.backing().keys().collect::<Vec<_>>()
+    fn description(&self) -> String {
+        format!("it contains the digit {}{}", self.digit, in_base(self.base))
     }
+}
+
+
+/// whether the digits sum to an even number (in binary, whether the
+/// population count is even)
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct DigitSumParityHypothesis {
+    pub even: bool,
+    pub base: u16
+}
 
-    pub fn belief(&self, hypothesis: H) -> f64 {
-        *self.backing().get(&hypothesis).unwrap_or(&0.0f64)
+impl DigitSumParityHypothesis {
+    pub fn new(even: bool, base: u16) -> Self {
+        DigitSumParityHypothesis { even, base }
     }
 
-    pub fn entropy(&self) -> f64 {
-        self.backing().values().map(|p| -p * p.log2()).sum()
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::DigitSumParity(self)
+    }
+}
+
+impl Hypothesis<Study> for DigitSumParityHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let sum: u16 = digits(*study, self.base).iter().sum();
+        sum.is_multiple_of(2) == self.even
     }
 
-    pub fn completely_certain(&self) -> Option<H> {
-        if self.backing().len() != 1 {
-            None
+    fn description(&self) -> String {
+        let parity = if self.even { "even" } else { "odd" };
+        if self.base == 2 {
+            format!("it has an {} number of 1s in binary", parity)
         } else {
-            Some(*self.backing().keys().nth(0).expect("should have one entry"))
+            format!("its digits{} sum to an {} number",
+                    in_base(self.base), parity)
+        }
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct PalindromeHypothesis {
+    pub base: u16
+}
+
+impl PalindromeHypothesis {
+    pub fn new(base: u16) -> Self {
+        PalindromeHypothesis { base }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Palindrome(self)
+    }
+}
+
+impl Hypothesis<Study> for PalindromeHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let digits = digits(*study, self.base);
+        digits.iter().eq(digits.iter().rev())
+    }
+
+    fn description(&self) -> String {
+        format!("it is a palindrome{}", in_base(self.base))
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct IncreasingDigitsHypothesis {
+    pub base: u16
+}
+
+impl IncreasingDigitsHypothesis {
+    pub fn new(base: u16) -> Self {
+        IncreasingDigitsHypothesis { base }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::IncreasingDigits(self)
+    }
+}
+
+impl Hypothesis<Study> for IncreasingDigitsHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        digits(*study, self.base).windows(2).all(|pair| pair[0] < pair[1])
+    }
+
+    fn description(&self) -> String {
+        format!("its digits{} are strictly increasing", in_base(self.base))
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum BasicHypothesis {
+    Divisibility(DivisibilityHypothesis),
+    Boundedness(BoundednessHypothesis),
+    Prime(PrimeHypothesis),
+    PerfectPower(PerfectPowerHypothesis),
+    Power(PowerHypothesis),
+    Triangular(TriangularHypothesis),
+    Fibonacci(FibonacciHypothesis),
+    DivisorCount(DivisorCountHypothesis),
+    Residue(ResidueHypothesis),
+    PrimeFactorCount(PrimeFactorCountHypothesis),
+    Squarefree(SquarefreeHypothesis),
+    ContainsDigit(ContainsDigitHypothesis),
+    DigitSumParity(DigitSumParityHypothesis),
+    Palindrome(PalindromeHypothesis),
+    IncreasingDigits(IncreasingDigitsHypothesis)
+}
+
+impl Hypothesis<Study> for BasicHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        match *self {
+            BasicHypothesis::Divisibility(h) => h.predicts_the_property(study),
+            BasicHypothesis::Boundedness(h) => h.predicts_the_property(study),
+            BasicHypothesis::Prime(h) => h.predicts_the_property(study),
+            BasicHypothesis::PerfectPower(h) => h.predicts_the_property(study),
+            BasicHypothesis::Power(h) => h.predicts_the_property(study),
+            BasicHypothesis::Triangular(h) => h.predicts_the_property(study),
+            BasicHypothesis::Fibonacci(h) => h.predicts_the_property(study),
+            BasicHypothesis::DivisorCount(h) => h.predicts_the_property(study),
+            BasicHypothesis::Residue(h) => h.predicts_the_property(study),
+            BasicHypothesis::PrimeFactorCount(h) =>
+                h.predicts_the_property(study),
+            BasicHypothesis::Squarefree(h) => h.predicts_the_property(study),
+            BasicHypothesis::ContainsDigit(h) =>
+                h.predicts_the_property(study),
+            BasicHypothesis::DigitSumParity(h) =>
+                h.predicts_the_property(study),
+            BasicHypothesis::Palindrome(h) => h.predicts_the_property(study),
+            BasicHypothesis::IncreasingDigits(h) =>
+                h.predicts_the_property(study)
+        }
+    }
+    fn description(&self) -> String {
+        match *self {
+            BasicHypothesis::Divisibility(h) => h.description(),
+            BasicHypothesis::Boundedness(h) => h.description(),
+            BasicHypothesis::Prime(h) => h.description(),
+            BasicHypothesis::PerfectPower(h) => h.description(),
+            BasicHypothesis::Power(h) => h.description(),
+            BasicHypothesis::Triangular(h) => h.description(),
+            BasicHypothesis::Fibonacci(h) => h.description(),
+            BasicHypothesis::DivisorCount(h) => h.description(),
+            BasicHypothesis::Residue(h) => h.description(),
+            BasicHypothesis::PrimeFactorCount(h) => h.description(),
+            BasicHypothesis::Squarefree(h) => h.description(),
+            BasicHypothesis::ContainsDigit(h) => h.description(),
+            BasicHypothesis::DigitSumParity(h) => h.description(),
+            BasicHypothesis::Palindrome(h) => h.description(),
+            BasicHypothesis::IncreasingDigits(h) => h.description()
+        }
+    }
+}
+
+
+/// a basic criterion for numbers, or the conjunction or disjunction of two
+pub type JoinedHypothesis = hypothesis::JoinedHypothesis<BasicHypothesis>;
+
+pub type Remainder = hypothesis::Remainder<BasicHypothesis>;
+
+pub type Distribution<H> = distribution::Distribution<H, Study>;
+
+
+/// the base, besides ten, in which to entertain criteria about digits
+const ALTERNATE_BASE: u16 = 2;
+
+
+/// the numbers below `end` (exclusive) that the criterion picks out
+fn extension(basic: &BasicHypothesis, end: u16) -> Vec<Study> {
+    (1..end).filter(|n| basic.predicts_the_property(n)).collect()
+}
+
+/// divisibility and boundedness criteria for numbers not greater than
+/// `bound`, and their conjunctions and disjunctions, along with criteria of
+/// arithmetic structure and about digits
+pub fn hypothesis_space(bound: u16) -> Vec<JoinedHypothesis> {
+    let bound = bound + 1; // convenience with exclusive ranges
+    let mut hypotheses = Vec::new();
+    // (the numbers within the bound that each criterion so far picks out)
+    let mut predictions = HashSet::new();
+
+    // divisibility
+    for divisor in 1..bound/2 {
+        let basic = DivisibilityHypothesis::new(divisor).to_basic();
+        predictions.insert(extension(&basic, bound));
+        hypotheses.push(JoinedHypothesis::full_stop(basic));
+    }
+
+    // boundedness
+    for min in 2..bound-1 {
+        for max in min..bound {
+            let basic = BoundednessHypothesis::new(min, max).to_basic();
+            predictions.insert(extension(&basic, bound));
+            hypotheses.push(JoinedHypothesis::full_stop(basic));
+        }
+    }
+
+    // arithmetic structure and digits (omitting any criterion that would
+    // hold for every number within the bound or none, or that picks out just
+    // the same numbers as one already included)
+    let mut structural = vec![PrimeHypothesis.to_basic(),
+                              PerfectPowerHypothesis::new(2).to_basic(),
+                              PerfectPowerHypothesis::new(3).to_basic(),
+                              TriangularHypothesis.to_basic(),
+                              FibonacciHypothesis.to_basic()];
+    for base in 2..bound/2 {
+        structural.push(PowerHypothesis::new(base).to_basic());
+    }
+    for count in 1..bound/2 {
+        structural.push(DivisorCountHypothesis::new(count).to_basic());
+    }
+    structural.push(SquarefreeHypothesis.to_basic());
+    let most_prime_factors = (1..bound)
+        .map(|n| sieve().prime_factors(n).len() as u16)
+        .max().unwrap_or(0);
+    for count in 1..most_prime_factors + 1 {
+        for &distinct in &[false, true] {
+            structural.push(
+                PrimeFactorCountHypothesis::new(count, distinct).to_basic());
+        }
+    }
+    for modulus in 2..bound/2 {
+        for remainder in 1..modulus {
+            structural.push(
+                ResidueHypothesis::new(modulus, remainder).to_basic());
         }
     }
+    for &base in &[10, ALTERNATE_BASE] {
+        for digit in 0..base {
+            structural.push(
+                ContainsDigitHypothesis::new(digit, base).to_basic());
+        }
+        for &even in &[true, false] {
+            structural.push(
+                DigitSumParityHypothesis::new(even, base).to_basic());
+        }
+        structural.push(PalindromeHypothesis::new(base).to_basic());
+        structural.push(IncreasingDigitsHypothesis::new(base).to_basic());
+    }
+    for basic in structural {
+        let prediction = extension(&basic, bound);
+        if !prediction.is_empty() && prediction.len() < (bound - 1) as usize &&
+                predictions.insert(prediction) {
+            hypotheses.push(JoinedHypothesis::full_stop(basic));
+        }
+    }
+
+    // conjunctions and disjunctions of divisibility and boundedness
+    //
+    // This approach is a little uglier for users than actually reasoning about
+    // ranges (it'll choose the first encountered of allowable bounds for a
+    // prediction, rather than the tightest bound), but it's more generalizable
+    for divisor in 1..bound/2 {
+        for min in 2..bound-1 {
+            for max in min..bound {
+                let multiples = (1..bound)
+                    .map(|i| i*divisor)
+                    .take_while(|&n| n < bound)
+                    .collect::<HashSet<_>>();
+                let range = (min..max+1).collect::<HashSet<_>>();
 
-    pub fn predict(&self, study: Study, verdict: bool) -> f64 {
-        self.backing().iter()
-            .filter(|hp| {
-                let h = hp.0;
-                h.predicts_the_property(study) == verdict
-            })
-            .map(|hp| {
-                let p = hp.1;
-                p
-            }).sum()
-    }
-
-    pub fn updated(&self, study: Study, verdict: bool) -> Self {
-        let normalization_factor = 1.0/self.predict(study, verdict);
-        let rebacking_pairs = self.backing()
-            .into_iter().filter(|hp| {
-                let h = hp.0;
-                h.predicts_the_property(study) == verdict
-            }).map(|hp| {
-                let (h, p) = hp;
-                (*h, normalization_factor * p)
-            });
-        let rebacking = HashMap::from_iter(rebacking_pairs);
-        Distribution(rebacking)
-    }
-
-    pub fn value_of_information(&self, study: Study) -> f64 {
-        let given_the_property = self.updated(study, true);
-        let given_the_negation = self.updated(study, false);
-        let expected_entropy =
-            self.predict(study, true) * given_the_property.entropy() +
-            self.predict(study, false) * given_the_negation.entropy();
-        self.entropy() - expected_entropy
-    }
-
-    pub fn burning_question(&self, studies: Vec<Study>) -> Option<Study> {
-        // CONSIDER: maybe this should just return a Study, and
-        // panic if the distribution is empty?
-        let mut top_value = NEG_INFINITY;
-        let mut best_subject: Option<Study> = None;
-        for study in &studies {
-            let value = self.value_of_information(*study);
-            if value > top_value {
-                top_value = value;
-                best_subject = Some(*study);
+                let mut conjunctive_prediction = multiples.intersection(&range)
+                    .cloned().collect::<Vec<_>>();
+                conjunctive_prediction.sort();
+                let mut disjunctive_prediction = multiples.union(&range)
+                    .cloned().collect::<Vec<_>>();
+                disjunctive_prediction.sort();
+
+                // (leaving out any that picks out just the same numbers as
+                // a criterion already included, whether the divisibility
+                // or boundedness criterion alone, a structural one, or an
+                // earlier conjunction or disjunction)
+                if predictions.insert(conjunctive_prediction) {
+                    hypotheses.push(
+                        JoinedHypothesis::and(
+                            DivisibilityHypothesis::new(divisor).to_basic(),
+                            BoundednessHypothesis::new(min, max).to_basic())
+                    );
+                }
+                if predictions.insert(disjunctive_prediction) {
+                    hypotheses.push(
+                        JoinedHypothesis::or(
+                            DivisibilityHypothesis::new(divisor).to_basic(),
+                            BoundednessHypothesis::new(min, max).to_basic())
+                    );
+                }
             }
         }
-        best_subject
     }
 
+    hypotheses
 }
 
 
-fn factorize_on_system(k: u16) -> Vec<u16> {
-    let stdout = Command::new("/usr/bin/factor").arg(format!("{}", k))
-        .output().ok().expect("couldn't factor").stdout;
-    let output = String::from_utf8(stdout).ok().expect("couldn't decode");
-    let trimmed_output = output.trim();
-    let output_parts = trimmed_output.split(": ");
-    let output_result = output_parts.skip(1).next().unwrap();
-    output_result.split(' ').map(
-        |c| { c.parse::<u16>().ok().unwrap() }).collect::<Vec<_>>()
+/// natural numbers not greater than `bound`
+pub struct NumberDomain {
+    pub bound: u16
+}
+
+impl Domain for NumberDomain {
+    type Study = Study;
+    type Hypothesis = JoinedHypothesis;
+
+    fn sample_study<R: Rng>(&self, rng: &mut R) -> Study {
+        rng.gen_range(1, self.bound + 1)
+    }
+
+    fn enumerate_studies(&self) -> Option<Vec<Study>> {
+        Some((1..self.bound + 1).collect())
+    }
+
+    fn prior<R: Rng>(&self, _rng: &mut R) -> Distribution<JoinedHypothesis> {
+        Distribution::ignorance_prior(hypothesis_space(self.bound))
+    }
+}
+
+
+/// The divisors `d` for which learning that `study` does (`verdict = true`)
+/// or doesn't have the property counts against "it is divisible by `d`",
+/// among the divisibility criteria entertained for numbers not greater than
+/// `bound`.
+pub fn divisibility_eliminated(study: Study, verdict: bool, bound: u16)
+                               -> Vec<u16> {
+    let divisors = sieve().divisors(study);
+    (1..bound.div_ceil(2))
+        .filter(|divisor| divisors.contains(divisor) != verdict)
+        .collect()
 }
 
 
 #[cfg(test)]
 mod tests {
-    use super::{BoundednessHypothesis, DivisibilityHypothesis,
-                Distribution, Hypothesis,
-                factorize_on_system};
+    use std::collections::HashSet;
+
+    use super::{BoundednessHypothesis, ContainsDigitHypothesis,
+                DigitSumParityHypothesis, DivisibilityHypothesis,
+                DivisorCountHypothesis, Distribution, FibonacciHypothesis,
+                Hypothesis, IncreasingDigitsHypothesis, JoinedHypothesis,
+                PalindromeHypothesis, PerfectPowerHypothesis,
+                PowerHypothesis, PrimeFactorCountHypothesis, PrimeHypothesis,
+                ResidueHypothesis, Sieve, SquarefreeHypothesis,
+                TriangularHypothesis, divisibility_eliminated,
+                hypothesis_space};
+    use inference::strategy::{ExpectedInformation, Minimax};
+    use rand::{SeedableRng, StdRng};
 
     #[test]
-    fn concerning_factorizing_on_the_system() {
-        assert_eq!(vec![2, 2, 3, 5], factorize_on_system(60));
-        assert_eq!(vec![2, 5, 5], factorize_on_system(50));
+    fn concerning_factorizing() {
+        let sieve = Sieve::new(100);
+        assert_eq!(vec![2, 2, 3, 5], sieve.prime_factors(60));
+        assert_eq!(vec![2, 5, 5], sieve.prime_factors(50));
+        assert_eq!(Vec::<u16>::new(), sieve.prime_factors(1));
+        assert_eq!(Vec::<u16>::new(), sieve.prime_factors(0));
+        assert_eq!(vec![97], sieve.prime_factors(97));
+        assert!(sieve.is_prime(97) && !sieve.is_prime(91));
+        assert_eq!(vec![1, 2, 3, 4, 6, 12], sieve.divisors(12));
+        assert_eq!(12, sieve.divisor_count(60));
+        assert_eq!(1, sieve.divisor_count(1));
+        assert!((1..101).all(|n| {
+            sieve.divisors(n) ==
+                (1..n + 1).filter(|d| n % d == 0).collect::<Vec<_>>()
+        }));
+    }
+
+    #[test]
+    fn concerning_factor_structure() {
+        assert!(PrimeFactorCountHypothesis::new(3, false)
+                .predicts_the_property(&12));
+        assert!(PrimeFactorCountHypothesis::new(2, true)
+                .predicts_the_property(&12));
+        assert!(!SquarefreeHypothesis.predicts_the_property(&12));
+        assert!(SquarefreeHypothesis.predicts_the_property(&30));
+        // 0 is neither prime nor squarefree, and has no particular number
+        // of divisors or prime factors
+        assert!(!PrimeHypothesis.predicts_the_property(&0));
+        assert!(!SquarefreeHypothesis.predicts_the_property(&0));
+        assert!((1..7).all(|count| {
+            !DivisorCountHypothesis::new(count).predicts_the_property(&0)
+        }));
+        assert!((1..4).all(|count| {
+            !PrimeFactorCountHypothesis::new(count, false)
+                .predicts_the_property(&0)
+        }));
+        assert_eq!("it has exactly 2 distinct prime factors",
+                   PrimeFactorCountHypothesis::new(2, true).description());
+    }
+
+    #[test]
+    fn concerning_which_divisibility_hypotheses_an_answer_eliminates() {
+        // (divisibility by 1 through 14 is entertained up to 30)
+        assert_eq!(vec![1, 2, 3, 4, 6, 12],
+                   divisibility_eliminated(12, false, 30));
+        assert_eq!(vec![5, 7, 8, 9, 10, 11, 13, 14],
+                   divisibility_eliminated(12, true, 30));
     }
 
     #[test]
@@ -325,7 +899,7 @@ mod tests {
         // If we learn that 15 does not have the property, then the 3
         // and 5 hypotheses are eliminated, and instead we think that
         // n = 2, 7, or 11 are equally likely.
-        let beliefs = prior.updated(15, false);
+        let beliefs = prior.updated(&15, false);
 
         let probability_n_is_two = beliefs.belief(
             DivisibilityHypothesis::new(2));
@@ -341,7 +915,34 @@ mod tests {
 
         // And we think that 14 has a 2/3 chance of having the
         // property.
-        assert_eq!(beliefs.predict(14, true), 2./3.);
+        assert_eq!(beliefs.predict(&14, true), 2./3.);
+    }
+
+    #[test]
+    fn concerning_forgiveness_of_mistaken_answers() {
+        let hypotheses = [2, 3, 5].iter().map(
+            |n| DivisibilityHypothesis::new(*n)).collect::<Vec<_>>();
+        let prior = Distribution::ignorance_prior(hypotheses)
+            .with_error_rate(0.1);
+
+        // If we're told (perhaps mistakenly) that 15 doesn't have the
+        // property, divisibility by 3 and 5 become less likely, but aren't
+        // ruled out.
+        let beliefs = prior.updated(&15, false);
+        assert_eq!(3, beliefs.len());
+        assert!(beliefs.belief(DivisibilityHypothesis::new(3)) > 0.);
+        let (best_hypothesis, best_probability) = beliefs.best_guess()
+            .expect("should have a best guess");
+        assert_eq!(DivisibilityHypothesis::new(2), best_hypothesis);
+        assert!((best_probability - 0.9/1.1).abs() < 1e-9);
+
+        // And an answer that contradicts every hypothesis doesn't leave us
+        // with nothing to believe.
+        let bewildered = beliefs.updated(&7, true);
+        assert_eq!(3, bewildered.len());
+        assert!((bewildered.hypotheses().iter()
+                 .map(|&&h| bewildered.belief(h)).sum::<f64>() - 1.).abs()
+                < 1e-9);
     }
 
     #[test]
@@ -350,14 +951,133 @@ mod tests {
             |n| DivisibilityHypothesis::new(n)).collect::<Vec<_>>();
         let prior = Distribution::ignorance_prior(hypotheses);
 
-        assert_eq!(prior.burning_question(vec![57, 60]).unwrap(), 60);
+        let seed: &[_] = &[1729];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        assert_eq!(Some(60),
+                   prior.burning_question_by(&[57, 60], &ExpectedInformation,
+                                             &mut rng));
+        // (60 is divisible by more of the candidates, so it leaves fewer
+        // standing whatever the answer)
+        assert_eq!(Some(60),
+                   prior.burning_question_by(&[57, 60], &Minimax, &mut rng));
     }
 
     #[test]
     fn concerning_making_a_heterogenous_hypothesis_vector() {
-        let mut hypotheses:  Vec<Box<Hypothesis>> = Vec::new();
-        hypotheses.push(Box::new(DivisibilityHypothesis::new(2)));
-        hypotheses.push(Box::new(BoundednessHypothesis::new_lower(2)));
+        let _hypotheses: Vec<Box<dyn Hypothesis<u16>>> = vec![
+            Box::new(DivisibilityHypothesis::new(2)),
+            Box::new(BoundednessHypothesis::new_lower(2))
+        ];
+    }
+
+    #[test]
+    fn concerning_arithmetic_structure() {
+        fn examples<H: Hypothesis<u16>>(hypothesis: H) -> Vec<u16> {
+            (1..30).filter(|n| hypothesis.predicts_the_property(n)).collect()
+        }
+        assert_eq!(vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29],
+                   examples(PrimeHypothesis));
+        assert_eq!(vec![1, 4, 9, 16, 25],
+                   examples(PerfectPowerHypothesis::new(2)));
+        assert_eq!(vec![1, 8, 27], examples(PerfectPowerHypothesis::new(3)));
+        assert_eq!(vec![1, 3, 9, 27], examples(PowerHypothesis::new(3)));
+        assert_eq!(vec![1, 3, 6, 10, 15, 21, 28],
+                   examples(TriangularHypothesis));
+        assert_eq!(vec![1, 2, 3, 5, 8, 13, 21], examples(FibonacciHypothesis));
+        assert_eq!(vec![6, 8, 10, 14, 15, 21, 22, 26, 27],
+                   examples(DivisorCountHypothesis::new(4)));
+        assert_eq!(vec![1, 5, 9, 13, 17, 21, 25, 29],
+                   examples(ResidueHypothesis::new(4, 1)));
+        assert_eq!("it is congruent to 1 modulo 4",
+                   ResidueHypothesis::new(4, 1).description());
+
+        let hypotheses = hypothesis_space(30);
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(
+            ResidueHypothesis::new(4, 1).to_basic())));
+        // (the numbers with exactly two divisors are the primes)
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(
+            PrimeHypothesis.to_basic())));
+        assert!(!hypotheses.contains(&JoinedHypothesis::full_stop(
+            DivisorCountHypothesis::new(2).to_basic())));
+    }
+
+    #[test]
+    fn concerning_the_distinctness_of_the_hypotheses() {
+        for &bound in &[30, 50] {
+            let mut predictions = HashSet::new();
+            for hypothesis in hypothesis_space(bound) {
+                let prediction = (1..bound + 1)
+                    .filter(|n| hypothesis.predicts_the_property(n))
+                    .collect::<Vec<_>>();
+                assert!(predictions.insert(prediction),
+                        "{:?} picks out the same numbers within {} as \
+                         another criterion", hypothesis, bound);
+            }
+        }
+
+        // (within 30, only 16 has exactly 5 divisors, which a boundedness
+        // criterion already picks out; only 24 and 30 have exactly 8, as
+        // would divisibility by 6 conjoined with 19 to 30, but structural
+        // criteria come before the conjunctions)
+        let hypotheses = hypothesis_space(30);
+        assert!(!hypotheses.contains(&JoinedHypothesis::full_stop(
+            DivisorCountHypothesis::new(5).to_basic())));
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(
+            DivisorCountHypothesis::new(8).to_basic())));
+        assert!(!hypotheses.contains(&JoinedHypothesis::and(
+            DivisibilityHypothesis::new(6).to_basic(),
+            BoundednessHypothesis::new(19, 30).to_basic())));
+
+        // (likewise, only 30 has 3 distinct prime factors, and only 16 and
+        // 24 have 4 prime factors in all)
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(
+            PrimeFactorCountHypothesis::new(3, true).to_basic())));
+        assert!(!hypotheses.contains(&JoinedHypothesis::and(
+            DivisibilityHypothesis::new(2).to_basic(),
+            BoundednessHypothesis::new(29, 30).to_basic())));
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(
+            PrimeFactorCountHypothesis::new(4, false).to_basic())));
+        assert!(!hypotheses.contains(&JoinedHypothesis::and(
+            DivisibilityHypothesis::new(8).to_basic(),
+            BoundednessHypothesis::new(9, 24).to_basic())));
+    }
+
+    #[test]
+    fn concerning_digits() {
+        assert!(ContainsDigitHypothesis::new(7, 10)
+                .predicts_the_property(&17));
+        assert!(!ContainsDigitHypothesis::new(7, 10)
+                .predicts_the_property(&16));
+        assert!(DigitSumParityHypothesis::new(true, 10)
+                .predicts_the_property(&28));
+        // (13 is 1101 in binary)
+        assert!(DigitSumParityHypothesis::new(false, 2)
+                .predicts_the_property(&13));
+        assert!(PalindromeHypothesis::new(10).predicts_the_property(&22));
+        assert!(PalindromeHypothesis::new(2).predicts_the_property(&9));
+        assert!(!PalindromeHypothesis::new(2).predicts_the_property(&10));
+        assert!(IncreasingDigitsHypothesis::new(10)
+                .predicts_the_property(&129));
+        assert!(!IncreasingDigitsHypothesis::new(10)
+                .predicts_the_property(&11));
+        assert_eq!("it has an odd number of 1s in binary",
+                   DigitSumParityHypothesis::new(false, 2).description());
+        assert_eq!("its digits are strictly increasing",
+                   IncreasingDigitsHypothesis::new(10).description());
+
+        let hypotheses = hypothesis_space(30);
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(
+            ContainsDigitHypothesis::new(2, 10).to_basic())));
+        // (within the bound, the numbers containing a 7 are just those
+        // congruent to 7 modulo 10)
+        assert!(!hypotheses.contains(&JoinedHypothesis::full_stop(
+            ContainsDigitHypothesis::new(7, 10).to_basic())));
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(
+            PalindromeHypothesis::new(2).to_basic())));
+        // (nor, within the bound, are the numbers containing a 0 any but
+        // the multiples of 10, which divisibility already picks out)
+        assert!(!hypotheses.contains(&JoinedHypothesis::full_stop(
+            ContainsDigitHypothesis::new(0, 10).to_basic())));
     }
 
 }
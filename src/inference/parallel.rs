@@ -0,0 +1,47 @@
+use std::cmp;
+use std::thread;
+
+
+pub fn thread_count() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+
+/// Apply `f` to each item, dividing the items among as many threads as we
+/// have cores. The results come back in the order of the items, so that
+/// callers who break ties by position get the same answer however the work
+/// happened to be scheduled.
+pub fn parallel_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+    where T: Sync, R: Send, F: Fn(&T) -> R + Sync {
+    if items.is_empty() {
+        return Vec::new();
+    }
+    let threads = thread_count();
+    let chunk_size = cmp::max(1, items.len().div_ceil(threads));
+    let f = &f;
+    thread::scope(|scope| {
+        let handles = items.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || {
+                chunk.iter().map(f).collect::<Vec<_>>()
+            }))
+            .collect::<Vec<_>>();
+        handles.into_iter()
+            .flat_map(|handle| handle.join().expect("worker thread panicked"))
+            .collect()
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::parallel_map;
+
+    #[test]
+    fn concerning_the_order_of_parallel_results() {
+        let items = (0..1000u32).collect::<Vec<_>>();
+        let squares = parallel_map(&items, |&i| i * i);
+        assert_eq!(items.iter().map(|&i| i * i).collect::<Vec<_>>(), squares);
+        assert!(parallel_map(&Vec::<u32>::new(), |&i| i).is_empty());
+    }
+
+}
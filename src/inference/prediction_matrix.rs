@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use inference::bitset::Bitset;
+use inference::hypothesis::Hypothesis;
+use inference::parallel::parallel_map;
+
+
+fn predictions_on<H: Hypothesis<S>, S>(hypotheses: &[H], study: &S)
+                                       -> Bitset {
+    hypotheses.iter()
+        .map(|hypothesis| hypothesis.predicts_the_property(study))
+        .collect()
+}
+
+
+/// Which hypotheses (of type `H`) predict the property of which studies (of
+/// type `S`), computed once for a pool of candidate studies so that choosing
+/// a question doesn't require re-running `predicts_the_property` for every
+/// hypothesis on every candidate on every turn. A `Distribution` consults it
+/// in place of the hypotheses themselves when asked about the pool.
+pub struct PredictionMatrix<H, S> {
+    hypotheses: Vec<H>,
+    // the index of each hypothesis in `hypotheses`
+    indices: BTreeMap<H, usize>,
+    studies: Vec<S>,
+    // bit i of `predictions[j]` is set iff hypothesis i predicts that study j
+    // has the property
+    predictions: Vec<Bitset>
+}
+
+impl<H: Hypothesis<S> + Ord + Copy, S> PredictionMatrix<H, S> {
+    pub fn new(hypotheses: Vec<H>, studies: Vec<S>) -> Self
+        where H: Sync, S: Sync {
+        let predictions = parallel_map(
+            &studies, |study| predictions_on(&hypotheses, study));
+        let indices = hypotheses.iter().enumerate()
+            .map(|(index, &hypothesis)| (hypothesis, index))
+            .collect();
+        PredictionMatrix { hypotheses, indices, studies, predictions }
+    }
+
+    pub fn hypotheses(&self) -> &[H] {
+        &self.hypotheses
+    }
+
+    pub fn studies(&self) -> &[S] {
+        &self.studies
+    }
+
+    /// which hypotheses predict that the `index`th study has the property
+    pub fn predictions(&self, index: usize) -> &Bitset {
+        &self.predictions[index]
+    }
+
+    /// whether the hypothesis predicts that the `index`th study has the
+    /// property (looked up, if the hypothesis is one of ours)
+    pub fn predicts(&self, hypothesis: &H, index: usize) -> bool {
+        match self.indices.get(hypothesis) {
+            Some(&hypothesis_index) => {
+                self.predictions[index].get(hypothesis_index)
+            },
+            None => hypothesis.predicts_the_property(&self.studies[index])
+        }
+    }
+
+    /// The hypotheses with any probability, grouped into classes of equal
+    /// probability (up to rounding): the probability of each member, and the
+    /// membership mask. There are only a few distinct weights in a
+    /// complexity prior, and an update rescales all the hypotheses that
+    /// agree with the answer by the same factor, so there are only a few
+    /// classes, and the probability mass agreeing with a study is a sum of
+    /// popcounts weighted by class.
+    pub fn classes<F>(&self, belief: F) -> Vec<(f64, Bitset)>
+        where F: Fn(H) -> f64 {
+        let mut weighted = self.hypotheses.iter().enumerate()
+            .map(|(index, &hypothesis)| (belief(hypothesis), index))
+            .filter(|&(probability, _)| probability > 0.)
+            .collect::<Vec<_>>();
+        weighted.sort_by(|&(p, _), &(q, _)| {
+            p.partial_cmp(&q).expect("probabilities should be comparable")
+        });
+        let mut classes: Vec<(f64, Bitset)> = Vec::new();
+        for (probability, index) in weighted {
+            let joins_last = match classes.last() {
+                Some(&(q, _)) => probability - q <= 1e-12 * probability,
+                None => false
+            };
+            if !joins_last {
+                classes.push((probability,
+                              Bitset::new(self.hypotheses.len())));
+            }
+            classes.last_mut().expect("should have a class").1.set(index);
+        }
+        classes
+    }
+}
+
+
+/// How to look one question further ahead when choosing what to ask: only
+/// the `candidates` pool studies with the most information of their own are
+/// considered (both as the question and as the question after), and only
+/// as many as can be scored within `time_budget`.
+#[derive(Debug, Copy, Clone)]
+pub struct Lookahead {
+    pub candidates: usize,
+    pub time_budget: Duration
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+    use inference::distribution::Domain;
+    use inference::strategy::ExpectedInformation;
+    use inference::triangle::{complexity_prior, standard_basic_hypotheses,
+                              TriangleDomain};
+
+    #[test]
+    fn concerning_agreement_with_the_hypotheses_themselves() {
+        let mut rng = thread_rng();
+        let prior = complexity_prior(standard_basic_hypotheses(), &mut rng);
+        let hypotheses = prior.hypotheses().into_iter()
+            .cloned().collect::<Vec<_>>();
+        let matrix = PredictionMatrix::new(
+            hypotheses, TriangleDomain.candidate_studies(50, &mut rng));
+
+        let splits = prior.splits_over(&matrix);
+        for (index, study) in matrix.studies().iter().enumerate() {
+            assert!((prior.predict(study, true) -
+                     prior.predict_at(&matrix, index, true)).abs() < 1e-9);
+            assert!((prior.value_of_information(study) -
+                     splits[index].value_of_information).abs() < 1e-9);
+            assert_eq!(prior.split_on(study).property_count,
+                       splits[index].property_count);
+        }
+
+        let study = &matrix.studies()[0];
+        let verdict = prior.predict(study, true) > 0.;
+        let updated = prior.updated(study, verdict);
+        let pooled_updated = prior.updated_at(&matrix, 0, verdict);
+        assert_eq!(updated.hypotheses(), pooled_updated.hypotheses());
+        for hypothesis in updated.hypotheses() {
+            assert!((updated.belief(*hypothesis) -
+                     pooled_updated.belief(*hypothesis)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn concerning_agreement_when_answers_might_be_mistaken() {
+        let mut rng = thread_rng();
+        let prior = complexity_prior(standard_basic_hypotheses(), &mut rng)
+            .with_error_rate(0.05);
+        let hypotheses = prior.hypotheses().into_iter()
+            .cloned().collect::<Vec<_>>();
+        let matrix = PredictionMatrix::new(
+            hypotheses, TriangleDomain.candidate_studies(20, &mut rng));
+        let mut beliefs = prior;
+        for index in 0..5 {
+            beliefs = beliefs.updated_at(&matrix, index, index % 2 == 0);
+        }
+        // (mistaken answers split the classes of the prior, but not into
+        // more classes than there are counts of disagreements)
+        let classes = matrix.classes(|hypothesis| beliefs.belief(hypothesis));
+        assert!(classes.len() <= 2 * 6);
+        let splits = beliefs.splits_over(&matrix);
+        for (index, study) in matrix.studies().iter().enumerate() {
+            assert!((beliefs.value_of_information(study) -
+                     splits[index].value_of_information).abs() < 1e-6);
+        }
+        let question = beliefs.burning_question_in(
+            &matrix, &ExpectedInformation, &mut rng)
+            .expect("should have a question");
+        let top_value = splits.iter()
+            .map(|split| split.value_of_information)
+            .fold(0., f64::max);
+        assert_eq!(top_value, splits[question].value_of_information);
+    }
+
+    #[test]
+    fn concerning_looking_ahead() {
+        let mut rng = thread_rng();
+        let prior = complexity_prior(standard_basic_hypotheses(), &mut rng);
+        let hypotheses = prior.hypotheses().into_iter()
+            .cloned().collect::<Vec<_>>();
+        let matrix = PredictionMatrix::new(
+            hypotheses, TriangleDomain.candidate_studies(100, &mut rng));
+        let beliefs = prior.updated_at(&matrix, 0, true);
+        let greedy = beliefs.burning_question_in(
+            &matrix, &ExpectedInformation, &mut rng);
+
+        // with only one candidate, or no time to consider any, we're greedy
+        let myopic = Lookahead { candidates: 1,
+                                 time_budget: Duration::from_secs(60) };
+        assert_eq!(greedy,
+                   beliefs.burning_question_looking_ahead(&matrix, &myopic));
+        let hasty = Lookahead { candidates: 100,
+                                time_budget: Duration::from_secs(0) };
+        assert_eq!(greedy,
+                   beliefs.burning_question_looking_ahead(&matrix, &hasty));
+
+        let patient = Lookahead { candidates: 20,
+                                  time_budget: Duration::from_secs(60) };
+        let question = beliefs.burning_question_looking_ahead(
+            &matrix, &patient).expect("should have a question");
+        assert!(beliefs.worth_asking(
+            beliefs.value_of_information(&matrix.studies()[question])));
+    }
+
+}
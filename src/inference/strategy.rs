@@ -0,0 +1,190 @@
+use rand::Rng;
+
+
+/// How the hypotheses still in the running divide over a candidate study:
+/// everything a strategy needs to know to decide whether to ask about it.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Split {
+    pub property_mass: f64,
+    pub negation_mass: f64,
+    pub property_count: usize,
+    pub negation_count: usize,
+    /// the expected reduction in entropy from asking
+    pub value_of_information: f64,
+    /// whether the engine would think the question worth asking at all
+    pub worth_asking: bool
+}
+
+
+/// A way of choosing which of several candidate studies to ask about.
+pub trait Strategy: Sync {
+    fn name(&self) -> &'static str;
+
+    /// how much we'd like to ask about a study that splits the hypotheses
+    /// like this (higher is better)
+    fn score(&self, split: &Split) -> f64;
+
+    /// whether the strategy is after the greatest expected information, so
+    /// that looking ahead, or searching beyond the pool, for questions that
+    /// tell us more serves it
+    fn seeks_information(&self) -> bool {
+        false
+    }
+
+    /// the index of the split to ask about: the best-scoring of those
+    /// worth asking (the first, if there's a tie), or if none are, the
+    /// best-scoring of all
+    fn choose(&self, splits: &[Split], _rng: &mut dyn Rng) -> Option<usize> {
+        let mut best: Option<(usize, bool, f64)> = None;
+        for (index, split) in splits.iter().enumerate() {
+            let score = self.score(split);
+            let better = match best {
+                None => true,
+                Some((_, top_worth_asking, top_score)) => {
+                    (split.worth_asking, score) > (top_worth_asking, top_score)
+                }
+            };
+            if better {
+                best = Some((index, split.worth_asking, score));
+            }
+        }
+        best.map(|(index, _, _)| index)
+    }
+}
+
+
+/// the expected reduction in entropy (the original criterion)
+pub struct ExpectedInformation;
+
+impl Strategy for ExpectedInformation {
+    fn name(&self) -> &'static str { "information" }
+
+    fn score(&self, split: &Split) -> f64 {
+        split.value_of_information
+    }
+
+    fn seeks_information(&self) -> bool {
+        true
+    }
+}
+
+
+/// predicted probabilities of the answers as close to even as possible
+pub struct BalancedSplit;
+
+impl Strategy for BalancedSplit {
+    fn name(&self) -> &'static str { "balanced" }
+
+    fn score(&self, split: &Split) -> f64 {
+        let total_mass = split.property_mass + split.negation_mass;
+        if total_mass == 0. {
+            return f64::NEG_INFINITY;
+        }
+        -(split.property_mass - split.negation_mass).abs() / total_mass
+    }
+}
+
+
+/// as few hypotheses as possible remaining, whatever the answer
+pub struct Minimax;
+
+impl Strategy for Minimax {
+    fn name(&self) -> &'static str { "minimax" }
+
+    fn score(&self, split: &Split) -> f64 {
+        -(split.property_count.max(split.negation_count) as f64)
+    }
+}
+
+
+/// any question worth asking at all (as a baseline)
+pub struct RandomQuestion;
+
+impl Strategy for RandomQuestion {
+    fn name(&self) -> &'static str { "random" }
+
+    fn score(&self, _split: &Split) -> f64 {
+        0.
+    }
+
+    fn choose(&self, splits: &[Split], rng: &mut dyn Rng) -> Option<usize> {
+        let worth_asking = splits.iter().enumerate()
+            .filter(|&(_, split)| split.worth_asking)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        if worth_asking.is_empty() {
+            if splits.is_empty() { None } else { Some(0) }
+        } else {
+            // (`gen_range` needs a sized generator, which a reference to
+            // one is)
+            let mut rng = rng;
+            let choice = Rng::gen_range(&mut rng, 0, worth_asking.len());
+            Some(worth_asking[choice])
+        }
+    }
+}
+
+
+pub const STRATEGY_NAMES: [&str; 4] = [
+    "information", "balanced", "minimax", "random"];
+
+pub fn strategy_named(name: &str) -> Result<Box<dyn Strategy>, String> {
+    match name {
+        "information" => Ok(Box::new(ExpectedInformation)),
+        "balanced" => Ok(Box::new(BalancedSplit)),
+        "minimax" => Ok(Box::new(Minimax)),
+        "random" => Ok(Box::new(RandomQuestion)),
+        _ => Err(format!("unknown strategy {:?} (expected one of: {})",
+                         name, STRATEGY_NAMES.join(", ")))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, StdRng};
+
+    fn split(property_count: usize, negation_count: usize,
+             value_of_information: f64) -> Split {
+        let total = (property_count + negation_count) as f64;
+        Split {
+            property_mass: property_count as f64 / total,
+            negation_mass: negation_count as f64 / total,
+            property_count,
+            negation_count,
+            value_of_information,
+            worth_asking: value_of_information > 0.
+        }
+    }
+
+    #[test]
+    fn concerning_the_choices_of_strategies() {
+        let seed: &[_] = &[1729];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let splits = vec![split(10, 0, 0.), split(1, 9, 0.47),
+                          split(6, 4, 0.97), split(5, 5, 0.9)];
+        // (the last is contrived to disagree with the entropy, to tell
+        // the strategies apart)
+        assert_eq!(Some(2), ExpectedInformation.choose(&splits, &mut rng));
+        assert_eq!(Some(3), BalancedSplit.choose(&splits, &mut rng));
+        assert_eq!(Some(3), Minimax.choose(&splits, &mut rng));
+        for _ in 0..10 {
+            let choice = RandomQuestion.choose(&splits, &mut rng).unwrap();
+            assert!(choice != 0);
+        }
+
+        let uninformative = vec![split(3, 0, 0.), split(0, 3, 0.)];
+        assert_eq!(Some(0), Minimax.choose(&uninformative, &mut rng));
+        assert_eq!(Some(0), RandomQuestion.choose(&uninformative, &mut rng));
+        assert!(strategy_named("telepathy").is_err());
+        assert_eq!("minimax", strategy_named("minimax").unwrap().name());
+        assert_eq!(vec!["information"],
+                   STRATEGY_NAMES.iter()
+                   .filter(|name| {
+                       strategy_named(name).unwrap().seeks_information()
+                   })
+                   .cloned().collect::<Vec<_>>());
+    }
+
+}
@@ -2,7 +2,7 @@ use inference::triangle::Hypothesis;
 use triangles::{Color, Study};
 
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
 pub struct ColorCountBoundednessHypothesis {
     pub color: Color,
     pub lower: Option<usize>,
@@ -36,7 +36,7 @@ impl ColorCountBoundednessHypothesis {
 }
 
 
-impl Hypothesis for ColorCountBoundednessHypothesis {
+impl Hypothesis<Study> for ColorCountBoundednessHypothesis {
     fn predicts_the_property(&self, study: &Study) -> bool {
         let color_count = study.color_count(self.color);
         if let Some(min) = self.lower {
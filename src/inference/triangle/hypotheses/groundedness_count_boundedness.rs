@@ -2,7 +2,7 @@ use inference::triangle::Hypothesis;
 use triangles::Study;
 
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
 pub struct GroundednessCountBoundednessHypothesis {
     pub grounded: bool,
     pub lower: Option<usize>,
@@ -37,7 +37,7 @@ impl GroundednessCountBoundednessHypothesis {
 }
 
 
-impl Hypothesis for GroundednessCountBoundednessHypothesis {
+impl Hypothesis<Study> for GroundednessCountBoundednessHypothesis {
     fn predicts_the_property(&self, study: &Study) -> bool {
         let groundedness_count = study.groundedness_count(self.grounded);
         if let Some(min) = self.lower {
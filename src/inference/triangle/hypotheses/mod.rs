@@ -5,6 +5,7 @@ pub mod pip_boundedness;
 pub mod pip_parity;
 pub mod standard_basics;
 
+use inference::hypothesis;
 use inference::triangle::Hypothesis;
 use inference::triangle::hypotheses::color_count_boundedness::ColorCountBoundednessHypothesis;
 use inference::triangle::hypotheses::size_count_boundedness::SizeCountBoundednessHypothesis;
@@ -15,7 +16,7 @@ use inference::triangle::hypotheses::pip_parity::PipParityHypothesis;
 use triangles::Study;
 
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
 pub enum BasicHypothesis {
     ColorCountBoundedness(ColorCountBoundednessHypothesis),
     SizeCountBoundedness(SizeCountBoundednessHypothesis),
@@ -56,6 +57,19 @@ impl From<PipParityHypothesis> for BasicHypothesis {
 
 
 impl BasicHypothesis {
+    /// the name of the kind of criterion this is, setting aside its
+    /// parameters
+    pub fn family(&self) -> &'static str {
+        match *self {
+            BasicHypothesis::ColorCountBoundedness(_) => "color count",
+            BasicHypothesis::SizeCountBoundedness(_) => "size count",
+            BasicHypothesis::GroundednessCountBoundedness(_) =>
+                "groundedness count",
+            BasicHypothesis::PipBoundedness(_) => "pip count",
+            BasicHypothesis::PipParity(_) => "pip parity",
+        }
+    }
+
     pub fn obviates(&self, other: &BasicHypothesis) -> bool {
         match *self {
             BasicHypothesis::ColorCountBoundedness(h1) => match *other {
@@ -95,7 +109,7 @@ impl BasicHypothesis {
 }
 
 
-impl Hypothesis for BasicHypothesis {
+impl Hypothesis<Study> for BasicHypothesis {
     // XXX: the amount of boilerplate quasi-duplicated code in this project is
     // out of control; it remains to be seen how much of it can be gotten under
     // control with macros and a better understanding of how to use Rust
@@ -125,87 +139,8 @@ impl Hypothesis for BasicHypothesis {
 }
 
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
-pub enum Remainder {
-    And(BasicHypothesis),
-    Or(BasicHypothesis),
-    FullStop
-}
-
-
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
-pub struct JoinedHypothesis {
-    pub proposition: BasicHypothesis,
-    pub remainder: Remainder
-}
-
-impl JoinedHypothesis {
-    pub fn full_stop(hypothesis: BasicHypothesis) -> Self {
-        JoinedHypothesis { proposition: hypothesis,
-                           remainder: Remainder::FullStop }
-    }
-
-    pub fn and(first_conjunct: BasicHypothesis,
-               second_conjunct: BasicHypothesis) -> Self {
-        JoinedHypothesis { proposition: first_conjunct,
-                           remainder: Remainder::And(second_conjunct) }
-    }
-
-    pub fn or(first_disjunct: BasicHypothesis,
-              second_disjunct: BasicHypothesis) -> Self {
-        JoinedHypothesis { proposition: first_disjunct,
-                           remainder: Remainder::Or(second_disjunct) }
-    }
+/// a basic criterion for triangle studies, or the conjunction or
+/// disjunction of two
+pub type JoinedHypothesis = hypothesis::JoinedHypothesis<BasicHypothesis>;
 
-    pub fn check_substantiality(&self, sample_cap: usize) -> bool {
-        let mut falsifiable = false;
-        let mut confirmable = false;
-        for _ in 0..sample_cap {
-            let study = Study::sample();
-            if self.predicts_the_property(&study) {
-                confirmable = true;
-            } else {
-                falsifiable = true;
-            }
-            if falsifiable && confirmable {
-                return true;
-            }
-        }
-        false
-    }
-}
-
-
-impl Hypothesis for JoinedHypothesis {
-    fn predicts_the_property(&self, study: &Study) -> bool {
-        match self.remainder {
-            Remainder::And(ref conjunct) => {
-                self.proposition.predicts_the_property(study) &&
-                    conjunct.predicts_the_property(study)
-            },
-            Remainder::Or(ref disjunct) => {
-                self.proposition.predicts_the_property(study) ||
-                    disjunct.predicts_the_property(study)
-            },
-            Remainder::FullStop => {
-                self.proposition.predicts_the_property(study)
-            }
-        }
-    }
-
-    fn description(&self) -> String {
-        match self.remainder {
-            Remainder::And(ref conjunct) => {
-                format!("{} and {}", self.proposition.description(),
-                        conjunct.description())
-            },
-            Remainder::Or(ref disjunct) => {
-                format!("{} or {}", self.proposition.description(),
-                        disjunct.description())
-            },
-            Remainder::FullStop => {
-                self.proposition.description()
-            }
-        }
-    }
-}
+pub type Remainder = hypothesis::Remainder<BasicHypothesis>;
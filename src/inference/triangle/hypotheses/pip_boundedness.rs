@@ -1,7 +1,7 @@
 use inference::triangle::Hypothesis;
 use triangles::Study;
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
 pub struct PipBoundednessHypothesis {
     pub lower: Option<usize>,
     pub upper: Option<usize>
@@ -39,7 +39,7 @@ impl PipBoundednessHypothesis {
 }
 
 
-impl Hypothesis for PipBoundednessHypothesis {
+impl Hypothesis<Study> for PipBoundednessHypothesis {
     fn predicts_the_property(&self, study: &Study) -> bool {
         let pip_count = study.pip_count();
         if let Some(min) = self.lower {
@@ -1,7 +1,7 @@
 use inference::triangle::Hypothesis;
 use triangles::Study;
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
 pub struct PipParityHypothesis {
     pub modulus: usize,
     pub remainder: usize,
@@ -13,7 +13,7 @@ impl PipParityHypothesis {
     }
 }
 
-impl Hypothesis for PipParityHypothesis {
+impl Hypothesis<Study> for PipParityHypothesis {
     fn predicts_the_property(&self, study: &Study) -> bool {
         study.pip_count() % self.modulus == self.remainder
     }
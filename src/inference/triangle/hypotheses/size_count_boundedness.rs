@@ -2,7 +2,7 @@ use inference::triangle::Hypothesis;
 use triangles::{Size, Study};
 
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
 pub struct SizeCountBoundednessHypothesis {
     pub size: Size,
     pub lower: Option<usize>,
@@ -37,7 +37,7 @@ impl SizeCountBoundednessHypothesis {
 }
 
 
-impl Hypothesis for SizeCountBoundednessHypothesis {
+impl Hypothesis<Study> for SizeCountBoundednessHypothesis {
     fn predicts_the_property(&self, study: &Study) -> bool {
         let size_count = study.size_count(self.size);
         if let Some(min) = self.lower {
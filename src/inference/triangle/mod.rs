@@ -2,177 +2,194 @@
 
 pub mod hypotheses;
 
-use std::collections::HashMap;
-use std::hash::Hash;
-use std::cmp::{Eq, Ordering};
-use std::iter::FromIterator;
+use std::cmp;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
 
-use ansi_term::Style;
+use rand::{Rng, SeedableRng, StdRng};
 
 use triangles::Study;
+use inference::distribution;
+use inference::distribution::{binary_entropy, Domain};
+pub use inference::hypothesis::Hypothesis;
+use inference::parallel::{parallel_map, thread_count};
+use inference::prediction_matrix::PredictionMatrix;
 use inference::triangle::hypotheses::BasicHypothesis;
-use inference::triangle::hypotheses::JoinedHypothesis;
+use inference::triangle::hypotheses::{JoinedHypothesis, Remainder};
 pub use inference::triangle::hypotheses::standard_basics::standard_basic_hypotheses;
 
-pub trait Hypothesis {
-    fn predicts_the_property(&self, study: &Study) -> bool;
-    fn description(&self) -> String;
-}
+/// how many candidate studies each thread evaluates at a time when searching
+/// for a question
+const QUESTION_BATCH_PER_THREAD: usize = 16;
 
+/// `burning_question` spends this fraction of its evaluations on random
+/// studies, and the rest searching locally around the best of them
+const RANDOM_SAMPLE_SHARE: f64 = 0.25;
 
-#[derive(Debug)]
-pub struct Distribution<H: Hypothesis + Hash + Eq>(HashMap<H, f64>);
+/// how many of the best pool studies (or random samples) to search locally
+/// around
+const LOCAL_SEARCH_STARTS: usize = 8;
 
-impl<H: Hypothesis + Hash + Eq + Copy> Distribution<H> {
-    pub fn new() -> Self {
-        let backing = HashMap::<H, f64>::new();
-        Distribution(backing)
-    }
+/// how many mutations to try from each of them when choosing a question
+pub const LOCAL_SEARCH_STEPS: usize = 100;
 
-    pub fn ignorance_prior(hypotheses: Vec<H>) -> Self {
-        let mut backing = HashMap::<H, f64>::new();
-        let probability_each: f64 = 1.0/(hypotheses.len() as f64);
-        for hypothesis in hypotheses.into_iter() {
-            backing.insert(hypothesis, probability_each);
-        }
-        Distribution(backing)
-    }
+/// a question expected to tell us this share of the most that an answer
+/// could is as good as we need, so we stop searching once we have one
+const SATISFYING_SHARE: f64 = 0.99;
 
-    fn backing(&self) -> &HashMap<H, f64> {
-        &self.0
-    }
-
-    fn mut_backing(&mut self) -> &mut HashMap<H, f64> {
-        &mut self.0
-    }
-
-    pub fn len(&self) -> usize {
-        self.backing().len()
-    }
-
-    pub fn hypotheses(&self) -> Vec<&H> {
-        self.backing().keys().collect::<Vec<_>>()
-    }
-
-    pub fn belief(&self, hypothesis: H) -> f64 {
-        *self.backing().get(&hypothesis).unwrap_or(&0.0f64)
-    }
+/// beliefs about which hypothesis concerning triangle studies is true
+pub type Distribution<H> = distribution::Distribution<H, Study>;
 
-    pub fn entropy(&self) -> f64 {
-        self.backing().values().map(|p| -p * p.log2()).sum()
-    }
+/// the game of triangle science: studies of stacked triangles, and the
+/// standard hypotheses about them, weighted by complexity
+pub struct TriangleDomain;
 
-    pub fn completely_certain(&self) -> Option<H> {
-        if self.backing().len() != 1 {
-            None
-        } else {
-            Some(*self.backing().keys().nth(0).expect("should have one entry"))
-        }
-    }
+impl Domain for TriangleDomain {
+    type Study = Study;
+    type Hypothesis = JoinedHypothesis;
 
-    pub fn predict(&self, study: &Study, verdict: bool) -> f64 {
-        self.backing().iter()
-            .filter(|hp| {
-                let h = hp.0;
-                h.predicts_the_property(study) == verdict
-            })
-            .map(|hp| {
-                let p = hp.1;
-                p
-            }).sum()
+    fn sample_study<R: Rng>(&self, rng: &mut R) -> Study {
+        Study::sample(rng)
     }
 
-    pub fn updated(&self, study: &Study, verdict: bool) -> Self {
-        let normalization_factor = 1.0/self.predict(study, verdict);
-        let rebacking_pairs = self.backing()
-            .into_iter().filter(|hp| {
-                let h = hp.0;
-                h.predicts_the_property(study) == verdict
-            }).map(|hp| {
-                let (h, p) = hp;
-                (*h, normalization_factor * p)
-            });
-        let rebacking = HashMap::from_iter(rebacking_pairs);
-        Distribution(rebacking)
+    fn prior<R: Rng>(&self, rng: &mut R) -> Distribution<JoinedHypothesis> {
+        complexity_prior(standard_basic_hypotheses(), rng)
     }
+}
 
-    pub fn value_of_information(&self, study: &Study) -> f64 {
-        let mut entropy = 0.;
-        let mut probability_of_the_property = 0.;
-        let mut probability_of_the_negation = 0.;
 
-        for (&hypothesis, &probability) in self.backing().iter() {
-            if hypothesis.predicts_the_property(study) {
-                probability_of_the_property += probability;
-            } else {
-                probability_of_the_negation += probability;
+impl<H: Hypothesis<Study> + Ord + Copy> Distribution<H> {
+    /// Look for a study expected to tell us more than `desired_bits`,
+    /// evaluating at most `sample_cap` of them, and return the best found.
+    /// We start by sampling at random; candidates are drawn in batches and
+    /// evaluated in parallel, but considered in the order they were drawn,
+    /// so the result is the same as if we had gone through them one at a
+    /// time. Then we hill-climb from the best few samples (in parallel, each
+    /// with its own generator seeded from `rng`, so that the result is
+    /// still reproducible), mutating a study at a time.
+    pub fn burning_question<R: Rng>(&self, desired_bits: f64,
+                                    sample_cap: usize, rng: &mut R)
+                                    -> Study where H: Send + Sync {
+        let batch_size = QUESTION_BATCH_PER_THREAD * thread_count();
+        let sample_cap = cmp::max(sample_cap, 1);
+        let random_cap = cmp::max(
+            (sample_cap as f64 * RANDOM_SAMPLE_SHARE) as usize, 1);
+        let mut sampled = Vec::new();
+        while sampled.len() < random_cap {
+            let batch = (0..cmp::min(batch_size, random_cap - sampled.len()))
+                .map(|_| Study::sample(rng))
+                .collect::<Vec<_>>();
+            let values = parallel_map(
+                &batch, |study| self.value_of_information(study));
+            for (study, value) in batch.into_iter().zip(values) {
+                if value > desired_bits {
+                    return study;
+                }
+                sampled.push((study, value));
             }
-            entropy += -probability * probability.log2();
         }
 
-        let property_normalization_factor = 1./probability_of_the_property;
-        let negation_normalization_factor = 1./probability_of_the_negation;
-
-        let mut entropy_given_the_property = 0.;
-        let mut entropy_given_the_negation = 0.;
+        // (a stable sort, so that ties go to the earlier sample)
+        sampled.sort_by(|&(_, v), &(_, w)| {
+            w.partial_cmp(&v).expect("values should be comparable")
+        });
+        sampled.truncate(LOCAL_SEARCH_STARTS);
+        let steps = (sample_cap - random_cap) / sampled.len();
+        let starts = sampled.into_iter()
+            .map(|(study, value)| (study, value, rng.gen::<usize>()))
+            .collect::<Vec<_>>();
+        let climbs = parallel_map(&starts, |&(ref study, value, seed)| {
+            self.hill_climb(study.clone(), value, desired_bits, steps, seed)
+        });
 
-        for (&hypothesis, &probability) in self.backing().iter() {
-            if hypothesis.predicts_the_property(study) {
-                let p = property_normalization_factor * probability;
-                entropy_given_the_property += -p * p.log2();
-            } else {
-                let p = negation_normalization_factor * probability;
-                entropy_given_the_negation += -p * p.log2();
+        let mut top_study = None;
+        let mut top_value = f64::NEG_INFINITY;
+        for (study, value) in climbs {
+            if value > desired_bits {
+                return study;
+            }
+            if value > top_value {
+                top_value = value;
+                top_study = Some(study);
             }
         }
-
-        let expected_entropy =
-            probability_of_the_property * entropy_given_the_property +
-            probability_of_the_negation * entropy_given_the_negation;
-
-        entropy - expected_entropy
+        top_study.expect("should have sampled at least one study")
     }
 
-    pub fn burning_question(&self, desired_bits: f64, sample_cap: usize)
-                            -> Study {
-        let mut study = Study::sample();
-        let mut value = self.value_of_information(&study);
-        let mut top_study = study.clone();
-        let mut top_value = value;
-        let mut samples = 1;
-        loop {
+    /// The best question we can find by searching locally around the pool
+    /// studies that tell us the most: from each of the best few, we
+    /// hill-climb for `steps` steps (in parallel, each with its own
+    /// generator seeded from `rng`, so that the result is still
+    /// reproducible), mutating a study at a time. If no climb gets anywhere,
+    /// that's the best of the pool (the first such, if there's a tie).
+    pub fn burning_question_near<R: Rng>(&self,
+                                         matrix: &PredictionMatrix<H, Study>,
+                                         steps: usize, rng: &mut R)
+                                         -> Option<Study>
+        where H: Send + Sync {
+        let desired_bits = SATISFYING_SHARE *
+            (1. - binary_entropy(self.error_rate()));
+        let mut ranked = self.splits_over(matrix).into_iter()
+            .map(|split| split.value_of_information)
+            .enumerate()
+            .collect::<Vec<_>>();
+        // (a stable sort, so that ties go to the earlier study)
+        ranked.sort_by(|&(_, v), &(_, w)| {
+            w.partial_cmp(&v).expect("values should be comparable")
+        });
+        ranked.truncate(LOCAL_SEARCH_STARTS);
+        let (top_index, mut top_value) = match ranked.first() {
+            Some(&best) => best,
+            None => { return None; }
+        };
+        let mut top_study = matrix.studies()[top_index].clone();
+
+        let starts = ranked.into_iter()
+            .map(|(index, value)| {
+                (matrix.studies()[index].clone(), value, rng.gen::<usize>())
+            })
+            .collect::<Vec<_>>();
+        let climbs = parallel_map(&starts, |&(ref study, value, seed)| {
+            self.hill_climb(study.clone(), value, desired_bits, steps, seed)
+        });
+        for (study, value) in climbs {
             if value > top_value {
                 top_value = value;
                 top_study = study;
             }
-            if (top_value > desired_bits) || (samples >= sample_cap) {
-                break;
-            }
-            study = Study::sample();
-            value = self.value_of_information(&study);
-            samples += 1;
         }
-        top_study
+        Some(top_study)
     }
 
-    pub fn inspect(&self, n: usize) {
-        let mut backing = self.backing().iter().collect::<Vec<_>>();
-        backing.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(Ordering::Equal));
-        let total_probability_mass: f64 = backing.iter()
-            .map(|hp| { hp.1 }).sum();
-        println!("Total probability mass: {:.6}", total_probability_mass);
-        println!("Top {} hypotheses:", n);
-        for &(&hypothesis, &probability) in backing.iter().take(n) {
-            wrapln!(" * {}: {}", hypothesis.description(),
-                    Style::new().bold().paint(&format!("{:.4}", probability)));
+    /// Repeatedly try a random mutation of the study, keeping it if it's at
+    /// least as good (so that we can wander across plateaus), until the
+    /// study is expected to tell us more than `desired_bits` or we've taken
+    /// `steps` steps.
+    fn hill_climb(&self, mut study: Study, mut value: f64, desired_bits: f64,
+                  steps: usize, seed: usize) -> (Study, f64) {
+        let seed: &[_] = &[seed];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        for _ in 0..steps {
+            if value > desired_bits {
+                break;
+            }
+            let neighbor = study.sample_neighbor(&mut rng);
+            let neighbor_value = self.value_of_information(&neighbor);
+            if neighbor_value >= value {
+                study = neighbor;
+                value = neighbor_value;
+            }
         }
+        (study, value)
     }
+
 }
 
 
-pub fn complexity_prior(basic_hypotheses: Vec<BasicHypothesis>)
+pub fn complexity_prior<R: Rng>(basic_hypotheses: Vec<BasicHypothesis>,
+                                rng: &mut R)
                                 -> Distribution<JoinedHypothesis> {
-    let mut prebacking = HashMap::<JoinedHypothesis, f64>::new();
+    let mut prebacking = BTreeMap::<JoinedHypothesis, f64>::new();
     // just a guess; we'll have to normalize later to get a real probability
     let weight_each_basic = (2./3.)/(basic_hypotheses.len() as f64);
     let weight_each_joined = (1./3.)/(basic_hypotheses.len().pow(2) as f64);
@@ -192,23 +209,137 @@ pub fn complexity_prior(basic_hypotheses: Vec<BasicHypothesis>)
             let conjunction = JoinedHypothesis::and(one_basic, another_basic);
             let disjunction = JoinedHypothesis::or(one_basic, another_basic);
             for &junction in &vec![conjunction, disjunction] {
-                if junction.check_substantiality(100) {
+                if junction.check_substantiality(&TriangleDomain, 100, rng) {
                     prebacking.insert(junction, weight_each_joined);
                 }
             }
         }
     }
 
-    let total_mass: f64 = prebacking.iter().map(|hp| { hp.1 }).sum();
-    let normalization_factor = 1.0/total_mass;
-    let backing_pairs = prebacking.into_iter()
-        .map(|hp| {
-            let (h, p) = hp;
-            (h, normalization_factor * p)
+    Distribution::from_weights(prebacking)
+}
+
+
+/// how many past sessions' worth of evidence the complexity prior counts
+/// for when it's blended with the rules inferred in past sessions
+const COMPLEXITY_PSEUDO_SESSIONS: f64 = 10.;
+
+/// A prior that blends `prior` (presumably the complexity prior) with what
+/// past sessions tell us about the rules humans choose. Each past rule
+/// counts towards its shape (a single criterion, a conjunction, or a
+/// disjunction), and each of its criteria towards their family and, within
+/// the family, towards their parameters; with a pseudocount of one for
+/// everything, that makes for a distribution over criteria, and a
+/// hypothesis's empirical weight is its shape's share times its criteria's.
+/// The more past rules there are, the more that empirical distribution
+/// counts against `prior`.
+pub fn empirical_prior(prior: &Distribution<JoinedHypothesis>,
+                       past_rules: &[JoinedHypothesis])
+                       -> Distribution<JoinedHypothesis> {
+    if past_rules.is_empty() {
+        return prior.clone();
+    }
+
+    let mut basics_by_family = BTreeMap::<&'static str, usize>::new();
+    let mut known_basics = BTreeMap::<BasicHypothesis, ()>::new();
+    for hypothesis in prior.hypotheses() {
+        for basic in hypothesis.basics() {
+            if known_basics.insert(basic, ()).is_none() {
+                *basics_by_family.entry(basic.family()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut shape_counts = BTreeMap::<usize, f64>::new();
+    let mut family_counts = BTreeMap::<&'static str, f64>::new();
+    let mut basic_counts = BTreeMap::<BasicHypothesis, f64>::new();
+    let mut basics_counted = 0.;
+    for rule in past_rules {
+        *shape_counts.entry(shape_of(rule)).or_insert(0.) += 1.;
+        for basic in rule.basics() {
+            *family_counts.entry(basic.family()).or_insert(0.) += 1.;
+            *basic_counts.entry(basic).or_insert(0.) += 1.;
+            basics_counted += 1.;
+        }
+    }
+    let shape_share = |hypothesis: &JoinedHypothesis| {
+        (shape_counts.get(&shape_of(hypothesis)).unwrap_or(&0.) + 1.) /
+            (past_rules.len() as f64 + 3.)
+    };
+    let basic_share = |basic: BasicHypothesis| {
+        let family_count = *family_counts.get(basic.family()).unwrap_or(&0.);
+        let family_share = (family_count + 1.) /
+            (basics_counted + basics_by_family.len() as f64);
+        family_share * (basic_counts.get(&basic).unwrap_or(&0.) + 1.) /
+            (family_count + basics_by_family[basic.family()] as f64)
+    };
+
+    let empirical_weights = prior.hypotheses().into_iter()
+        .map(|hypothesis| {
+            let weight = hypothesis.basics().into_iter()
+                .map(&basic_share)
+                .product::<f64>();
+            (*hypothesis, shape_share(hypothesis) * weight)
+        })
+        .collect::<Vec<_>>();
+    let empirical_mass: f64 = empirical_weights.iter()
+        .map(|&(_, weight)| weight)
+        .sum();
+    let empirical_share = past_rules.len() as f64 /
+        (past_rules.len() as f64 + COMPLEXITY_PSEUDO_SESSIONS);
+    let weights = empirical_weights.into_iter()
+        .map(|(hypothesis, weight)| {
+            (hypothesis,
+             (1. - empirical_share) * prior.belief(hypothesis) +
+             empirical_share * weight / empirical_mass)
         });
-    let backing = HashMap::from_iter(backing_pairs);
+    Distribution::from_weights(weights).with_error_rate(prior.error_rate())
+}
 
-    Distribution(backing)
+/// 0 for a single criterion, 1 for a conjunction, 2 for a disjunction
+fn shape_of(hypothesis: &JoinedHypothesis) -> usize {
+    match hypothesis.remainder {
+        Remainder::FullStop => 0,
+        Remainder::And(_) => 1,
+        Remainder::Or(_) => 2
+    }
+}
+
+
+/// studies with up to this many triangles are few enough to check
+/// exhaustively when looking for a counterexample
+const EXHAUSTIVE_TRIANGLE_COUNT: usize = 4;
+
+/// every study with up to `EXHAUSTIVE_TRIANGLE_COUNT` triangles, smallest
+/// first (enumerated once, since there are some hundred thousand of them
+/// and we look for counterexamples several times a turn)
+fn small_studies() -> &'static [Study] {
+    static SMALL_STUDIES: OnceLock<Vec<Study>> = OnceLock::new();
+    SMALL_STUDIES.get_or_init(|| {
+        (0..EXHAUSTIVE_TRIANGLE_COUNT+1)
+            .flat_map(Study::enumerate)
+            .collect()
+    })
+}
+
+/// A study on which two hypotheses disagree, if we can find one: the
+/// smallest, if there's one small enough for us to have checked every study
+/// of its size, and otherwise the first of up to `sample_cap` random
+/// studies. `None` suggests (but doesn't prove) that they're extensionally
+/// equal.
+pub fn find_disagreement<H, R>(one: &H, another: &H, sample_cap: usize,
+                               rng: &mut R) -> Option<Study>
+    where H: Hypothesis<Study>, R: Rng {
+    let disagree = |study: &Study| {
+        one.predicts_the_property(study) !=
+            another.predicts_the_property(study)
+    };
+    if let Some(study) = small_studies().iter().find(|study| disagree(study)) {
+        return Some(study.clone());
+    }
+    (0..sample_cap)
+        .map(|_| Study::sample(rng))
+        .find(|study| disagree(study))
 }
 
 
@@ -216,7 +347,10 @@ pub fn complexity_prior(basic_hypotheses: Vec<BasicHypothesis>)
 mod tests {
     use test::Bencher;
 
+    use rand::{SeedableRng, StdRng, thread_rng};
+
     use super::*;
+    use inference::strategy::ExpectedInformation;
     use triangles::{Color, Size, Stack, Study, Triangle};
     use inference::triangle::hypotheses::{BasicHypothesis, JoinedHypothesis};
     use inference::triangle::hypotheses::color_count_boundedness::ColorCountBoundednessHypothesis;
@@ -249,6 +383,115 @@ mod tests {
         assert_eq!(probability_c_is_green, 0.5);
     }
 
+    #[test]
+    fn concerning_the_reproducibility_of_questions() {
+        let seed: &[_] = &[1729];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let distribution = complexity_prior(standard_basic_hypotheses(),
+                                            &mut rng);
+        let matrix = standard_matrix(&distribution, 100, &mut rng);
+        let question = distribution.burning_question_near(&matrix, 10,
+                                                          &mut rng);
+
+        let mut rng_again: StdRng = SeedableRng::from_seed(seed);
+        let distribution_again = complexity_prior(standard_basic_hypotheses(),
+                                                  &mut rng_again);
+        let matrix_again = standard_matrix(&distribution_again, 100,
+                                           &mut rng_again);
+        let question_again = distribution_again.burning_question_near(
+            &matrix_again, 10, &mut rng_again);
+
+        assert_eq!(distribution.hypotheses(), distribution_again.hypotheses());
+        assert_eq!(question, question_again);
+
+        // (sampling afresh, too)
+        let sampled = distribution.burning_question(0.95, 100, &mut rng);
+        let sampled_again = distribution_again.burning_question(
+            0.95, 100, &mut rng_again);
+        assert_eq!(sampled, sampled_again);
+    }
+
+    #[test]
+    fn concerning_searching_locally_for_questions() {
+        let seed: &[_] = &[1729];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        // At least how many red triangles? The one-bit questions (with
+        // exactly six red) are vanishingly rare among random studies, but
+        // every red triangle added on the way there helps.
+        let hypotheses = (1..13)
+            .map(|n| ColorCountBoundednessHypothesis::new_lower(Color::Red, n))
+            .collect::<Vec<_>>();
+        let prior = Distribution::ignorance_prior(hypotheses.clone());
+        let matrix = PredictionMatrix::new(
+            hypotheses, TriangleDomain.candidate_studies(20, &mut rng));
+        assert!(matrix.studies().iter()
+                .all(|study| study.color_count(Color::Red) != 6));
+        let question = prior.burning_question_near(&matrix, 250, &mut rng)
+            .expect("should have a question");
+        assert_eq!(6, question.color_count(Color::Red));
+
+        let sampled = prior.burning_question(0.99, 2000, &mut rng);
+        assert_eq!(6, sampled.color_count(Color::Red));
+    }
+
+    #[test]
+    fn concerning_priors_learned_from_past_sessions() {
+        let seed: &[_] = &[1729];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let prior = complexity_prior(standard_basic_hypotheses(), &mut rng);
+        let unchanged = empirical_prior(&prior, &[]);
+        assert_eq!(prior.hypotheses(), unchanged.hypotheses());
+
+        let red = |n| BasicHypothesis::from(
+            ColorCountBoundednessHypothesis::new_lower(Color::Red, n));
+        let blue = BasicHypothesis::from(
+            ColorCountBoundednessHypothesis::new_lower(Color::Blue, 1));
+        let past_rules = vec![JoinedHypothesis::full_stop(red(1)),
+                              JoinedHypothesis::full_stop(red(2)),
+                              JoinedHypothesis::full_stop(red(1))];
+        let learned = empirical_prior(&prior, &past_rules);
+        assert_eq!(prior.len(), learned.len());
+        let total: f64 = learned.hypotheses().iter()
+            .map(|&&h| learned.belief(h))
+            .sum();
+        assert!((total - 1.).abs() < 1e-9);
+        // the favorite rule is more probable than before, and more probable
+        // than a rule of the same family never chosen, which in turn (by
+        // way of its family) is more probable than before
+        let favorite = JoinedHypothesis::full_stop(red(1));
+        let cousin = JoinedHypothesis::full_stop(blue);
+        assert!(learned.belief(favorite) > prior.belief(favorite));
+        assert!(learned.belief(favorite) > learned.belief(cousin));
+        assert!(learned.belief(cousin) > prior.belief(cousin));
+    }
+
+    #[test]
+    fn concerning_counterexamples() {
+        let mut rng = thread_rng();
+        let at_least_one_blue = JoinedHypothesis::full_stop(
+            BasicHypothesis::from(
+                ColorCountBoundednessHypothesis::new_lower(Color::Blue, 1)));
+        let at_least_two_blue = JoinedHypothesis::full_stop(
+            BasicHypothesis::from(
+                ColorCountBoundednessHypothesis::new_lower(Color::Blue, 2)));
+        let blue_and_blue = JoinedHypothesis::and(
+            BasicHypothesis::from(
+                ColorCountBoundednessHypothesis::new_lower(Color::Blue, 1)),
+            BasicHypothesis::from(
+                ColorCountBoundednessHypothesis::new_lower(Color::Blue, 1)));
+
+        // the smallest study that tells them apart has exactly one Blue
+        // triangle
+        let counterexample = find_disagreement(
+            &at_least_one_blue, &at_least_two_blue, 100, &mut rng)
+            .expect("should find a counterexample");
+        assert_eq!(1, counterexample.color_count(Color::Blue));
+        assert_eq!(1, counterexample.into_iter().count());
+
+        assert_eq!(None, find_disagreement(
+            &at_least_one_blue, &blue_and_blue, 100, &mut rng));
+    }
+
     #[ignore] // TODO investigate and repair test
     #[test]
     fn concerning_soundness_of_our_complexity_penalty() {
@@ -278,7 +521,7 @@ mod tests {
             BasicHypothesis::from(
                 ColorCountBoundednessHypothesis::new_lower(Color::Red, 1))
         ];
-        let distribution = complexity_prior(basics);
+        let distribution = complexity_prior(basics, &mut thread_rng());
 
         assert_eq!(1./3.,
                    distribution.belief(JoinedHypothesis::full_stop(
@@ -297,15 +540,18 @@ mod tests {
 
     #[bench]
     fn concerning_the_expense_of_updating(bencher: &mut Bencher) {
-        let distribution = complexity_prior(standard_basic_hypotheses());
+        let mut rng = thread_rng();
+        let distribution = complexity_prior(standard_basic_hypotheses(),
+                                            &mut rng);
         bencher.iter(|| {
-            distribution.updated(&Study::sample(), true);
+            distribution.updated(&Study::sample(&mut rng), true);
         });
     }
 
     #[bench]
     fn concerning_the_expense_of_computing_entropy(bencher: &mut Bencher) {
-        let distribution = complexity_prior(standard_basic_hypotheses());
+        let distribution = complexity_prior(standard_basic_hypotheses(),
+                                            &mut thread_rng());
         bencher.iter(|| {
             distribution.entropy()
         });
@@ -313,17 +559,79 @@ mod tests {
 
     #[bench]
     fn concerning_the_expense_of_prediction(bencher: &mut Bencher) {
-        let distribution = complexity_prior(standard_basic_hypotheses());
+        let mut rng = thread_rng();
+        let distribution = complexity_prior(standard_basic_hypotheses(),
+                                            &mut rng);
         bencher.iter(|| {
-            distribution.predict(&Study::sample(), true);
+            distribution.predict(&Study::sample(&mut rng), true);
         });
     }
 
     #[bench]
     fn concerning_the_expense_of_the_value(bencher: &mut Bencher) {
-        let distribution = complexity_prior(standard_basic_hypotheses());
+        let mut rng = thread_rng();
+        let distribution = complexity_prior(standard_basic_hypotheses(),
+                                            &mut rng);
+        bencher.iter(|| {
+            distribution.value_of_information(&Study::sample(&mut rng));
+        });
+    }
+
+    fn standard_matrix<R: Rng>(distribution: &Distribution<JoinedHypothesis>,
+                               pool_size: usize, rng: &mut R)
+                               -> PredictionMatrix<JoinedHypothesis, Study> {
+        let hypotheses = distribution.hypotheses().into_iter()
+            .cloned().collect::<Vec<_>>();
+        PredictionMatrix::new(
+            hypotheses, TriangleDomain.candidate_studies(pool_size, rng))
+    }
+
+    fn pooled_standard_distribution(pool_size: usize)
+        -> (Distribution<JoinedHypothesis>,
+            PredictionMatrix<JoinedHypothesis, Study>) {
+        let mut rng = thread_rng();
+        let distribution = complexity_prior(standard_basic_hypotheses(),
+                                            &mut rng);
+        let matrix = standard_matrix(&distribution, pool_size, &mut rng);
+        (distribution, matrix)
+    }
+
+    #[bench]
+    fn concerning_the_expense_of_pooled_updating(bencher: &mut Bencher) {
+        let (distribution, matrix) = pooled_standard_distribution(100);
+        let mut index = 0;
+        bencher.iter(|| {
+            index = (index + 1) % 100;
+            distribution.updated_at(&matrix, index, true)
+        });
+    }
+
+    #[bench]
+    fn concerning_the_expense_of_pooled_prediction(bencher: &mut Bencher) {
+        let (distribution, matrix) = pooled_standard_distribution(100);
+        let mut index = 0;
+        bencher.iter(|| {
+            index = (index + 1) % 100;
+            distribution.predict_at(&matrix, index, true)
+        });
+    }
+
+    #[bench]
+    fn concerning_the_expense_of_the_pooled_values(bencher: &mut Bencher) {
+        let (distribution, matrix) = pooled_standard_distribution(100);
+        bencher.iter(|| {
+            distribution.splits_over(&matrix)
+        });
+    }
+
+    #[bench]
+    fn concerning_the_expense_of_a_pooled_burning_question(
+            bencher: &mut Bencher) {
+        let (distribution, matrix) = pooled_standard_distribution(1000);
+        let mut rng = thread_rng();
         bencher.iter(|| {
-            distribution.value_of_information(&Study::sample());
+            distribution.burning_question_in(&matrix, &ExpectedInformation,
+                                             &mut rng)
         });
     }
 
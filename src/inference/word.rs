@@ -0,0 +1,378 @@
+use rand::Rng;
+
+use inference::distribution;
+use inference::distribution::Domain;
+use inference::hypothesis;
+use inference::hypothesis::joined_space;
+pub use inference::hypothesis::Hypothesis;
+
+
+pub type Study = String;
+
+/// the bundled list of words to ask about, one per line
+const WORD_LIST: &str = include_str!("words.txt");
+
+pub fn word_list() -> Vec<Study> {
+    WORD_LIST.lines()
+        .map(|line| line.trim().to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn is_vowel(letter: char) -> bool {
+    "aeiou".contains(letter)
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct LengthHypothesis {
+    pub lower: Option<usize>,
+    pub upper: Option<usize>
+}
+
+impl LengthHypothesis {
+    pub fn new(lower: usize, upper: usize) -> Self {
+        LengthHypothesis { lower: Some(lower), upper: Some(upper) }
+    }
+    pub fn new_lower(lower: usize) -> Self {
+        LengthHypothesis { lower: Some(lower), upper: None }
+    }
+    pub fn new_upper(upper: usize) -> Self {
+        LengthHypothesis { lower: None, upper: Some(upper) }
+    }
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Length(self)
+    }
+}
+
+impl Hypothesis<Study> for LengthHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let length = study.chars().count();
+        if let Some(min) = self.lower {
+            if length < min {
+                return false;
+            }
+        }
+        if let Some(max) = self.upper {
+            if length > max {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn description(&self) -> String {
+        if let (Some(min), Some(max)) = (self.lower, self.upper) {
+            if min == max {
+                return format!("it has exactly {} letters", min);
+            }
+        }
+        let mut described: Vec<String> = Vec::new();
+        if let Some(min) = self.lower {
+            described.push(format!("it has at least {} letters", min));
+        }
+        if self.lower.is_some() && self.upper.is_some() {
+            described.push("and".to_owned());
+        }
+        if let Some(max) = self.upper {
+            described.push(format!("it has at most {} letters", max));
+        }
+        described.join(" ")
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct EndpointHypothesis {
+    pub letter: char,
+    /// whether the word starts (rather than ends) with the letter
+    pub initial: bool
+}
+
+impl EndpointHypothesis {
+    pub fn new(letter: char, initial: bool) -> Self {
+        EndpointHypothesis { letter, initial }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Endpoint(self)
+    }
+}
+
+impl Hypothesis<Study> for EndpointHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let endpoint = if self.initial {
+            study.chars().next()
+        } else {
+            study.chars().last()
+        };
+        endpoint == Some(self.letter)
+    }
+
+    fn description(&self) -> String {
+        format!("it {} with '{}'",
+                if self.initial { "starts" } else { "ends" }, self.letter)
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct DoubleLetterHypothesis {
+    pub present: bool
+}
+
+impl DoubleLetterHypothesis {
+    pub fn new(present: bool) -> Self {
+        DoubleLetterHypothesis { present }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::DoubleLetter(self)
+    }
+}
+
+impl Hypothesis<Study> for DoubleLetterHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let letters = study.chars().collect::<Vec<_>>();
+        let doubled = letters.windows(2).any(|pair| pair[0] == pair[1]);
+        doubled == self.present
+    }
+
+    fn description(&self) -> String {
+        format!("it contains {} double letter",
+                if self.present { "a" } else { "no" })
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct VowelCountHypothesis {
+    pub count: usize,
+    /// whether the word has at least (rather than at most) `count` vowels
+    pub at_least: bool
+}
+
+impl VowelCountHypothesis {
+    pub fn new(count: usize, at_least: bool) -> Self {
+        VowelCountHypothesis { count, at_least }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::VowelCount(self)
+    }
+}
+
+impl Hypothesis<Study> for VowelCountHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let vowels = study.chars().filter(|&letter| is_vowel(letter)).count();
+        if self.at_least {
+            vowels >= self.count
+        } else {
+            vowels <= self.count
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("it has {} {} vowel{}",
+                if self.at_least { "at least" } else { "at most" },
+                self.count, if self.count == 1 { "" } else { "s" })
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct AlphabeticalHypothesis {
+    /// whether the letters run forwards (rather than backwards) through the
+    /// alphabet
+    pub ascending: bool
+}
+
+impl AlphabeticalHypothesis {
+    pub fn new(ascending: bool) -> Self {
+        AlphabeticalHypothesis { ascending }
+    }
+
+    pub fn to_basic(self) -> BasicHypothesis {
+        BasicHypothesis::Alphabetical(self)
+    }
+}
+
+impl Hypothesis<Study> for AlphabeticalHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        let letters = study.chars().collect::<Vec<_>>();
+        letters.windows(2).all(|pair| {
+            if self.ascending {
+                pair[0] <= pair[1]
+            } else {
+                pair[0] >= pair[1]
+            }
+        })
+    }
+
+    fn description(&self) -> String {
+        format!("its letters are in {}alphabetical order",
+                if self.ascending { "" } else { "reverse " })
+    }
+}
+
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum BasicHypothesis {
+    Length(LengthHypothesis),
+    Endpoint(EndpointHypothesis),
+    DoubleLetter(DoubleLetterHypothesis),
+    VowelCount(VowelCountHypothesis),
+    Alphabetical(AlphabeticalHypothesis)
+}
+
+impl Hypothesis<Study> for BasicHypothesis {
+    fn predicts_the_property(&self, study: &Study) -> bool {
+        match *self {
+            BasicHypothesis::Length(h) => h.predicts_the_property(study),
+            BasicHypothesis::Endpoint(h) => h.predicts_the_property(study),
+            BasicHypothesis::DoubleLetter(h) => h.predicts_the_property(study),
+            BasicHypothesis::VowelCount(h) => h.predicts_the_property(study),
+            BasicHypothesis::Alphabetical(h) => h.predicts_the_property(study)
+        }
+    }
+    fn description(&self) -> String {
+        match *self {
+            BasicHypothesis::Length(h) => h.description(),
+            BasicHypothesis::Endpoint(h) => h.description(),
+            BasicHypothesis::DoubleLetter(h) => h.description(),
+            BasicHypothesis::VowelCount(h) => h.description(),
+            BasicHypothesis::Alphabetical(h) => h.description()
+        }
+    }
+}
+
+
+/// a basic criterion for words, or the conjunction or disjunction of two
+pub type JoinedHypothesis = hypothesis::JoinedHypothesis<BasicHypothesis>;
+
+pub type Distribution<H> = distribution::Distribution<H, Study>;
+
+
+/// the most vowels to entertain as a bound
+const MAX_VOWEL_COUNT: usize = 3;
+
+/// length bounds, endpoint letters, double letters, vowel counts, and
+/// alphabetical ordering, as they apply to the given words
+pub fn standard_basic_hypotheses(words: &[Study]) -> Vec<BasicHypothesis> {
+    let lengths = words.iter().map(|word| word.chars().count());
+    let shortest = lengths.clone().min().unwrap_or(0);
+    let longest = lengths.max().unwrap_or(0);
+
+    let mut basics = Vec::new();
+    for min in shortest + 1..longest + 1 {
+        basics.push(LengthHypothesis::new_lower(min).to_basic());
+    }
+    for max in shortest..longest {
+        basics.push(LengthHypothesis::new_upper(max).to_basic());
+    }
+    for min in shortest + 1..longest {
+        for max in min..longest {
+            basics.push(LengthHypothesis::new(min, max).to_basic());
+        }
+    }
+    for letter in "abcdefghijklmnopqrstuvwxyz".chars() {
+        for &initial in &[true, false] {
+            basics.push(EndpointHypothesis::new(letter, initial).to_basic());
+        }
+    }
+    for &flag in &[true, false] {
+        basics.push(DoubleLetterHypothesis::new(flag).to_basic());
+        basics.push(AlphabeticalHypothesis::new(flag).to_basic());
+    }
+    for count in 1..MAX_VOWEL_COUNT + 1 {
+        for &at_least in &[true, false] {
+            basics.push(VowelCountHypothesis::new(count, at_least).to_basic());
+        }
+    }
+    basics
+}
+
+/// the standard criteria, and their conjunctions and disjunctions (omitting
+/// any that would accept every word or none, or that accepts just the same
+/// words as one already included)
+pub fn hypothesis_space(words: &[Study]) -> Vec<JoinedHypothesis> {
+    joined_space(&standard_basic_hypotheses(words), Vec::new(), words)
+}
+
+
+/// the words of a word list
+pub struct WordDomain {
+    pub words: Vec<Study>
+}
+
+impl WordDomain {
+    /// the words of the bundled list
+    pub fn bundled() -> Self {
+        WordDomain { words: word_list() }
+    }
+}
+
+impl Domain for WordDomain {
+    type Study = Study;
+    type Hypothesis = JoinedHypothesis;
+
+    fn sample_study<R: Rng>(&self, rng: &mut R) -> Study {
+        self.words[rng.gen_range(0, self.words.len())].clone()
+    }
+
+    fn enumerate_studies(&self) -> Option<Vec<Study>> {
+        Some(self.words.clone())
+    }
+
+    fn prior<R: Rng>(&self, _rng: &mut R) -> Distribution<JoinedHypothesis> {
+        Distribution::ignorance_prior(hypothesis_space(&self.words))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concerning_criteria_for_words() {
+        let coffee = "coffee".to_owned();
+        assert!(LengthHypothesis::new(5, 6).predicts_the_property(&coffee));
+        assert!(!LengthHypothesis::new_upper(5)
+                .predicts_the_property(&coffee));
+        assert!(EndpointHypothesis::new('c', true)
+                .predicts_the_property(&coffee));
+        assert!(!EndpointHypothesis::new('c', false)
+                .predicts_the_property(&coffee));
+        assert!(DoubleLetterHypothesis::new(true)
+                .predicts_the_property(&coffee));
+        assert!(VowelCountHypothesis::new(3, true)
+                .predicts_the_property(&coffee));
+        assert!(!AlphabeticalHypothesis::new(true)
+                .predicts_the_property(&coffee));
+        assert!(AlphabeticalHypothesis::new(true)
+                .predicts_the_property(&"almost".to_owned()));
+        assert!(AlphabeticalHypothesis::new(false)
+                .predicts_the_property(&"spoke".to_owned()));
+        assert_eq!("it has exactly 4 letters",
+                   LengthHypothesis::new(4, 4).description());
+        assert_eq!("it has at most 1 vowel",
+                   VowelCountHypothesis::new(1, false).description());
+    }
+
+    #[test]
+    fn concerning_the_bundled_word_list() {
+        let words = word_list();
+        assert!(words.len() > 300);
+        assert!(words.iter().all(|word| {
+            word.chars().all(|letter| letter.is_ascii_lowercase())
+        }));
+        let hypotheses = hypothesis_space(&words);
+        // (no word starts with 'x', so there's nothing to ask about)
+        assert!(!hypotheses.contains(&JoinedHypothesis::full_stop(
+            EndpointHypothesis::new('x', true).to_basic())));
+        assert!(hypotheses.contains(&JoinedHypothesis::full_stop(
+            EndpointHypothesis::new('s', true).to_basic())));
+    }
+
+}
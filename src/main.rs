@@ -7,6 +7,7 @@ extern crate test;
 
 #[macro_use] mod display;
 #[macro_use] mod triangles;
+mod cards;
 mod play;
 mod inference;
 
@@ -0,0 +1,62 @@
+use cards::Succession;
+use inference::card::CardDomain;
+use inference::strategy::Strategy;
+use rand::Rng;
+
+use play::domain;
+use play::domain::Presentation;
+use play::oracle::{OracleReport, Secret};
+use play::session::Session;
+use play::transcript::Transcript;
+
+
+impl Presentation for CardDomain {
+    fn welcome(&self) -> String {
+        format!("Welcome to Mezzanine v. {}! Privately think of a rule, in \
+                 the manner of Eleusis, for which cards may be played after \
+                 which: a rule that judges each card by how it relates to \
+                 the card before it. This program will attempt to \
+                 efficiently infer the nature of the rule by asking you \
+                 whether specific cards may follow others.",
+                env!("CARGO_PKG_VERSION"))
+    }
+
+    fn subject(&self) -> &'static str {
+        "a card may follow another"
+    }
+
+    fn encode(&self, study: &Succession) -> String {
+        study.to_notation()
+    }
+
+    fn decode(&self, encoded: &str) -> Result<Succession, String> {
+        Succession::from_notation(encoded)
+    }
+
+    fn proposition(&self, study: &Succession) -> String {
+        format!("{} may follow {}", study.card, study.previous)
+    }
+
+    fn question(&self, study: &Succession) -> String {
+        format!("May {} follow {}?", study.card, study.previous)
+    }
+
+    fn render(&self, study: &Succession) -> String {
+        format!("{}\n({})", study, study.to_notation())
+    }
+}
+
+
+pub fn play<R: Rng>(rng: &mut R, session: &mut Session,
+                    transcript: &mut Transcript, strategy: &dyn Strategy) {
+    domain::play(&CardDomain, rng, session, transcript, strategy);
+}
+
+
+/// Play a game against an automated oracle that answers according to a
+/// secret rule (mistakenly, with probability `error_rate`).
+pub fn play_oracle<R: Rng>(error_rate: f64, secret: &Secret,
+                           strategy: &dyn Strategy, rng: &mut R)
+                           -> Result<OracleReport, String> {
+    domain::play_oracle(&CardDomain, error_rate, secret, strategy, rng)
+}
@@ -0,0 +1,271 @@
+use std::io;
+use std::io::Write;
+
+use inference::distribution::{Distribution, Domain};
+use inference::hypothesis::Hypothesis;
+use inference::strategy::Strategy;
+use rand::Rng;
+
+use play::{parse_response, Response};
+use play::oracle::{answer, OracleReport, Secret};
+use play::session::Session;
+use play::transcript::{Exchange, Transcript};
+
+
+/// How many studies to consider asking about each time, in a domain with too
+/// many to consider them all.
+const CANDIDATE_STUDIES: usize = 1000;
+
+/// what to say when an answer (if the answers can't be mistaken) would leave
+/// no hypothesis standing
+pub const INCONSISTENT_ANSWER: &str =
+    "No criterion this program knows of is consistent with that answer \
+     and those before it, so it isn't taking the answer. Answer again, or \
+     take back an earlier answer with undo or rewind N.";
+
+
+/// How to talk with a human about a game's studies, so that it can be played
+/// by the same question-and-answer loop as any other.
+pub trait Presentation: Domain {
+    /// the greeting, explaining what to think of
+    fn welcome(&self) -> String;
+
+    /// what has the property, for stating a criterion ("… iff it is
+    /// divisible by 3")
+    fn subject(&self) -> &'static str;
+
+    /// the study's textual form, for saved sessions
+    fn encode(&self, study: &Self::Study) -> String;
+    fn decode(&self, encoded: &str) -> Result<Self::Study, String>;
+
+    /// the statement that the study has the property
+    fn proposition(&self, study: &Self::Study) -> String;
+
+    /// the question asking whether the study has the property
+    fn question(&self, study: &Self::Study) -> String;
+
+    /// the study as it should appear in a transcript
+    fn render(&self, study: &Self::Study) -> String;
+
+    /// something to say about an answer once it's given, such as what it
+    /// rules out (if the game has anything to say)
+    fn remark(&self, _study: &Self::Study, _verdict: bool) -> Option<String> {
+        None
+    }
+
+    /// a picture of what we believe, to show before each question (if the
+    /// game has one)
+    fn illustrate(&self,
+                  _beliefs: &Distribution<Self::Hypothesis, Self::Study>)
+                  -> Option<String> {
+        None
+    }
+}
+
+
+pub fn play<D, R>(domain: &D, rng: &mut R, session: &mut Session,
+                  transcript: &mut Transcript, strategy: &dyn Strategy)
+    where D: Presentation, R: Rng {
+    let error_rate = session.error_rate;
+    wrapln!("{}", domain.welcome());
+
+    let mut beliefs = domain.prior(rng).with_error_rate(error_rate);
+    println!("Size of hypothesis space: {}", beliefs.len());
+    let studies = domain.candidate_studies(CANDIDATE_STUDIES, rng);
+
+    // (each study asked about, the answer, and what we believed before
+    // hearing it)
+    let mut history = Vec::new();
+    let mut reasking = None;
+
+    for &(ref encoded_study, verdict) in &session.answers {
+        match domain.decode(encoded_study) {
+            Ok(study) => {
+                let updated_beliefs = beliefs.updated(&study, verdict);
+                if updated_beliefs.len() == 0 {
+                    wrapln!("The saved answer about {:?} is inconsistent \
+                             with those before it; resuming from just \
+                             before it.", encoded_study);
+                    break;
+                }
+                transcript.record(exchange(domain, &beliefs, &study, verdict));
+                history.push((study, verdict, beliefs));
+                beliefs = updated_beliefs;
+            },
+            Err(_) => {
+                wrapln!("The saved answer about {:?} isn't one this game \
+                         would have asked about; resuming from just \
+                         before it.", encoded_study);
+                break;
+            }
+        }
+    }
+    session.truncate(history.len());
+    if !history.is_empty() {
+        wrapln!("Resuming after {} answers.\n", history.len());
+    }
+
+    'questioning: loop {
+        if let Some(known_truth) = beliefs.completely_certain() {
+            let conclusion = format!("This program infers that {} iff {}.",
+                                     domain.subject(),
+                                     known_truth.description());
+            wrapln!("{}", conclusion);
+            transcript.conclude(conclusion, Vec::new());
+            return;
+        }
+
+        let study = match reasking.take() {
+            Some(study) => study,
+            None => beliefs.burning_question_by(&studies, strategy, rng)
+                .expect("should have studies to ask about")
+        };
+        let voi = beliefs.value_of_information(&study);
+        if !beliefs.worth_asking(voi) && error_rate > 0. {
+            let (best_guess, probability) = beliefs.best_guess()
+                .expect("should have a best guess");
+            let conclusion = format!(
+                "This program's best guess (with probability {:.3}) is that \
+                 {} iff {}.",
+                probability, domain.subject(), best_guess.description());
+            wrapln!("{}", conclusion);
+            transcript.conclude(conclusion, Vec::new());
+            return;
+        }
+        if !beliefs.worth_asking(voi) {
+            let conclusion = format!(
+                "This program has inferred all that it can, and is \
+                 indifferent between the following hypotheses concerning \
+                 when {}:", domain.subject());
+            wrapln!("{}", conclusion);
+            for hypothesis in beliefs.hypotheses() {
+                println!("  * {}", hypothesis.description());
+            }
+            transcript.conclude(
+                conclusion,
+                beliefs.hypotheses().iter()
+                    .map(|hypothesis| hypothesis.description())
+                    .collect());
+            return;
+        }
+        if let Some(illustration) = domain.illustrate(&beliefs) {
+            println!("{}", illustration);
+        }
+        wrapln!("This program's belief distribution (over {} remaining \
+                 hypotheses) has an entropy of {:.3} bits. Learning whether \
+                 {} is expected to reduce the entropy by {:.3} bits.",
+                 beliefs.len(), beliefs.entropy(), domain.proposition(&study),
+                 voi);
+        let mut verdict_maybe = None;
+        while verdict_maybe.is_none() {
+            print!("{} [Y/n/undo/rewind N/quit] >> ", domain.question(&study));
+            io::stdout().flush().expect("couldn't flush stdout?!");
+            let mut input_buffer = String::new();
+            io::stdin()
+                .read_line(&mut input_buffer)
+                .expect("couldn't read stdin!?");
+            verdict_maybe = match parse_response(&input_buffer) {
+                Some(Response::Verdict(verdict)) => Some(verdict),
+                Some(Response::Rewind(steps)) => {
+                    if steps == 0 || steps > history.len() {
+                        wrapln!("\nCan't take back {} answers when you've \
+                                 only given {}.", steps, history.len());
+                        continue;
+                    }
+                    let rewind_to = history.len() - steps;
+                    let (earlier_study, _, earlier_beliefs) =
+                        history.drain(rewind_to..).next()
+                        .expect("should have history to rewind");
+                    wrapln!("\nTaking back {} answer(s). Asking again \
+                             whether {}.\n",
+                            steps, domain.proposition(&earlier_study));
+                    session.truncate(rewind_to);
+                    transcript.truncate(rewind_to);
+                    beliefs = earlier_beliefs;
+                    reasking = Some(earlier_study);
+                    continue 'questioning;
+                },
+                Some(Response::Quit) => {
+                    wrapln!("\nStopping after {} answers.", history.len());
+                    return;
+                },
+                _ => {
+                    wrapln!("\nAnswer Y or n. You must comply.");
+                    continue;
+                },
+            };
+        }
+        let verdict = verdict_maybe.unwrap();
+        wrapln!("On the question of whether {}, you said {}.",
+                domain.proposition(&study), verdict);
+        let updated_beliefs = beliefs.updated(&study, verdict);
+        if updated_beliefs.len() == 0 {
+            wrapln!("{}\n", INCONSISTENT_ANSWER);
+            reasking = Some(study);
+            continue;
+        }
+        if let Some(remark) = domain.remark(&study, verdict) {
+            wrapln!("{}", remark);
+        }
+        println!();
+        session.record(domain.encode(&study), verdict);
+        transcript.record(exchange(domain, &beliefs, &study, verdict));
+        history.push((study, verdict, beliefs));
+        beliefs = updated_beliefs;
+    }
+}
+
+
+fn exchange<D: Presentation>(domain: &D,
+                             beliefs: &Distribution<D::Hypothesis, D::Study>,
+                             study: &D::Study, verdict: bool) -> Exchange {
+    Exchange {
+        rendered_study: domain.render(study),
+        hypothesis_count: beliefs.len(),
+        entropy: beliefs.entropy(),
+        value_of_information: beliefs.value_of_information(study),
+        verdict
+    }
+}
+
+
+/// Play a game against an automated oracle that answers according to a
+/// secret hypothesis (mistakenly, with probability `error_rate`).
+pub fn play_oracle<D, R>(domain: &D, error_rate: f64, secret: &Secret,
+                         strategy: &dyn Strategy, rng: &mut R)
+                         -> Result<OracleReport, String>
+    where D: Domain, R: Rng {
+    let mut beliefs = domain.prior(rng).with_error_rate(error_rate);
+    let hypotheses = beliefs.hypotheses().into_iter()
+        .cloned().collect::<Vec<_>>();
+    let secret = secret.choose(&hypotheses,
+                               |hypothesis| hypothesis.description(), rng)?;
+    let studies = domain.candidate_studies(CANDIDATE_STUDIES, rng);
+
+    let mut entropy_trajectory = vec![beliefs.entropy()];
+    while beliefs.completely_certain().is_none() {
+        let study = beliefs.burning_question_by(&studies, strategy, rng)
+            .expect("should have studies to ask about");
+        if !beliefs.worth_asking(beliefs.value_of_information(&study)) {
+            break;
+        }
+        let truth = secret.predicts_the_property(&study);
+        beliefs = beliefs.updated(&study, answer(truth, error_rate, rng));
+        entropy_trajectory.push(beliefs.entropy());
+    }
+
+    let (inference, confidence) = beliefs.best_guess()
+        .expect("should have a best guess");
+    let extensionally_equal = studies.iter().all(|study| {
+        inference.predicts_the_property(study) ==
+            secret.predicts_the_property(study)
+    });
+    Ok(OracleReport {
+        secret: secret.description(),
+        inference: inference.description(),
+        confidence,
+        questions: entropy_trajectory.len() - 1,
+        extensionally_equal,
+        entropy_trajectory
+    })
+}
@@ -0,0 +1,132 @@
+use inference::grid::{Distribution, GridDomain, Hypothesis, JoinedHypothesis,
+                      Point, Study};
+use inference::strategy::Strategy;
+use rand::Rng;
+
+use play::domain;
+use play::domain::Presentation;
+use play::oracle::{OracleReport, Secret};
+use play::session::Session;
+use play::transcript::Transcript;
+
+
+/// points to a side of the grid, unless a saved session says otherwise
+pub const GRID_SIZE: u16 = 8;
+
+/// the largest grid we'll play on (the rectangles alone grow as the fourth
+/// power of the size)
+pub const MAX_GRID_SIZE: u16 = 16;
+
+/// characters for ever greater probabilities that a point has the property
+const SHADES: [char; 10] = [' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+
+/// The grid, each point shaded by how probable it is that it has the
+/// property, with the origin at the bottom left.
+pub fn heat_map<H>(beliefs: &Distribution<H>, size: u16) -> String
+    where H: Hypothesis<Study> + Ord + Copy {
+    let mut rows = Vec::new();
+    for y in (0..size).rev() {
+        let mut row = format!("{:>2} |", y);
+        for x in 0..size {
+            let probability = beliefs.predict(&Point::new(x, y), true);
+            let shade = (probability * (SHADES.len() - 1) as f64).round();
+            row.push(' ');
+            row.push(SHADES[shade as usize]);
+        }
+        rows.push(row);
+    }
+    rows.push(format!("   +{}", "-".repeat(2 * size as usize + 1)));
+    rows.push(format!("    {}", (0..size)
+                      .map(|x| format!(" {}", x % 10))
+                      .collect::<String>()));
+    rows.push(format!("('{}': surely lacks the property; '{}': surely has \
+                       it)", SHADES[0], SHADES[SHADES.len() - 1]));
+    rows.join("\n")
+}
+
+
+impl Presentation for GridDomain {
+    fn welcome(&self) -> String {
+        format!("Welcome to Mezzanine v. {}! Privately think of a criterion \
+                 concerning points (x, y) on a grid, with coordinates from 0 \
+                 to {}—perhaps a half-plane, a rectangle, a diagonal, the \
+                 parity of x + y, or the distance from the origin. This \
+                 program will attempt to efficiently infer the nature of the \
+                 criterion by asking you whether specific points do or do \
+                 not have the property of satisfying it.",
+                env!("CARGO_PKG_VERSION"), self.size - 1)
+    }
+
+    fn subject(&self) -> &'static str {
+        "a point has the property"
+    }
+
+    fn encode(&self, study: &Study) -> String {
+        study.to_notation()
+    }
+
+    fn decode(&self, encoded: &str) -> Result<Study, String> {
+        let point = Point::from_notation(encoded)?;
+        if point.x < self.size && point.y < self.size {
+            Ok(point)
+        } else {
+            Err(format!("{} isn't on the grid", point))
+        }
+    }
+
+    fn proposition(&self, study: &Study) -> String {
+        format!("{} has the property", study)
+    }
+
+    fn question(&self, study: &Study) -> String {
+        format!("Does {} have the property?", study)
+    }
+
+    fn render(&self, study: &Study) -> String {
+        format!("{}", study)
+    }
+
+    fn illustrate(&self, beliefs: &Distribution<JoinedHypothesis>)
+                  -> Option<String> {
+        Some(heat_map(beliefs, self.size))
+    }
+}
+
+
+pub fn play<R: Rng>(rng: &mut R, size: u16, session: &mut Session,
+                    transcript: &mut Transcript, strategy: &dyn Strategy) {
+    domain::play(&GridDomain { size }, rng, session, transcript,
+                 strategy);
+}
+
+
+/// Play a game against an automated oracle that answers according to a
+/// secret criterion (mistakenly, with probability `error_rate`).
+pub fn play_oracle<R: Rng>(size: u16, error_rate: f64, secret: &Secret,
+                           strategy: &dyn Strategy, rng: &mut R)
+                           -> Result<OracleReport, String> {
+    domain::play_oracle(&GridDomain { size }, error_rate, secret,
+                        strategy, rng)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inference::grid::{Axis, HalfPlaneHypothesis};
+
+    #[test]
+    fn concerning_the_heat_map() {
+        let hypotheses = vec![HalfPlaneHypothesis::new_lower(Axis::X, 1),
+                              HalfPlaneHypothesis::new_lower(Axis::X, 2)];
+        let beliefs = Distribution::ignorance_prior(hypotheses);
+        let map = heat_map(&beliefs, 3);
+        let rows = map.lines().collect::<Vec<_>>();
+        assert_eq!(" 2 |   + @", rows[0]);
+        assert_eq!(" 0 |   + @", rows[2]);
+        assert_eq!("   +-------", rows[3]);
+        assert_eq!("     0 1 2", rows[4]);
+    }
+
+}
@@ -0,0 +1,285 @@
+use std::io;
+use std::io::Write;
+
+use rand::Rng;
+
+use inference::triangle::Hypothesis;
+use play::master::{display_labelled, Master};
+use triangles::Study;
+
+
+struct Player {
+    name: String,
+    // guessing stones, earned by calling mondo labels correctly and spent on
+    // guessing the rule
+    stones: usize,
+    mondo_calls: usize,
+    guesses: usize
+}
+
+impl Player {
+    fn new(name: String) -> Self {
+        Player { name, stones: 0, mondo_calls: 0, guesses: 0 }
+    }
+}
+
+
+/// what the player whose turn it is wants to do
+enum Turn {
+    /// have the master label a study
+    Master(Study),
+    /// have everyone guess the label of a study
+    Mondo(Study),
+    Guess(String),
+    ListRules(Vec<String>),
+    ListKoans,
+    Scores,
+    Pass,
+    Quit,
+}
+
+fn parse_turn(input: &str) -> Result<Turn, String> {
+    // (`read_line` gives us an empty string at the end of input)
+    if input.is_empty() {
+        return Ok(Turn::Quit);
+    }
+    let input = input.trim();
+    let mut words = input.split_whitespace();
+    let command = words.next();
+    let rest = match command {
+        Some(command) => input[command.len()..].trim(),
+        None => ""
+    };
+    match command {
+        Some("master") | Some("mondo") if rest.is_empty() => {
+            Err("Build a study to go with that.".to_owned())
+        },
+        Some("master") => Study::from_notation(rest).map(Turn::Master),
+        Some("mondo") => Study::from_notation(rest).map(Turn::Mondo),
+        Some("guess") => {
+            if rest.is_empty() {
+                Err("Guess what? Name a rule by number or description."
+                    .to_owned())
+            } else {
+                Ok(Turn::Guess(rest.to_owned()))
+            }
+        },
+        Some("rules") => Ok(Turn::ListRules(
+            words.map(|word| word.to_lowercase()).collect())),
+        Some("koans") => Ok(Turn::ListKoans),
+        Some("scores") => Ok(Turn::Scores),
+        Some("pass") => Ok(Turn::Pass),
+        Some("quit") => Ok(Turn::Quit),
+        _ => Err("Say `master` or `mondo` followed by a study, `guess` \
+                  followed by a rule, `rules`, `koans`, `scores`, `pass`, \
+                  or `quit`.".to_owned())
+    }
+}
+
+
+fn read_input(prompt: &str) -> String {
+    print!("{}", prompt);
+    io::stdout().flush().expect("couldn't flush stdout?!");
+    let mut input_buffer = String::new();
+    io::stdin()
+        .read_line(&mut input_buffer)
+        .expect("couldn't read stdin!?");
+    input_buffer
+}
+
+/// Clear the terminal, so that the students who call a mondo after this one
+/// can't see how this one called it.
+fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+    io::stdout().flush().expect("couldn't flush stdout?!");
+}
+
+/// what the criterion was, and how everyone did
+fn reveal(master: &Master, players: &[Player]) {
+    wrapln!("\nThe criterion was that a study has the property iff {}.",
+            master.rule().description());
+    display_scores(players);
+}
+
+fn display_scores(players: &[Player]) {
+    for player in players {
+        wrapln!("  {}: {} stone(s), {} correct mondo call(s), {} guess(es)",
+                player.name, player.stones, player.mondo_calls,
+                player.guesses);
+    }
+}
+
+
+/// Play Zendo for several students taking turns at the same terminal, with
+/// this program as the master.
+pub fn play<R: Rng>(rng: &mut R, names: Vec<String>) {
+    wrapln!("Welcome to Mezzanine v. {}! This program has privately chosen \
+             a criterion, and the students ({}) will take turns trying to \
+             discover it. On your turn, build a study (one stack at a time, \
+             separated by `|`, each triangle as the initial of its color \
+             followed by its size, from the ground up, e.g. `R3 B1 | Y2`) \
+             and say `master` to have this program label it, or `mondo` to \
+             have every student guess its label: those who guess correctly \
+             earn a guessing stone. Spend a stone on your turn to `guess` \
+             the criterion (by number or description; `rules` lists them). \
+             `koans` shows the labelled studies so far.\n",
+             env!("CARGO_PKG_VERSION"), names.join(", "));
+
+    let mut players = names.into_iter().map(Player::new).collect::<Vec<_>>();
+    let (master, positive_example, negative_example) = Master::new(rng);
+    display_labelled(&positive_example, true);
+    display_labelled(&negative_example, false);
+    let mut koans = vec![(positive_example, true), (negative_example, false)];
+
+    let mut turn = 0;
+    loop {
+        let current = turn % players.len();
+        let input = read_input(
+            &format!("{}'s turn ({} stone(s)) [master/mondo STUDY, guess \
+                      RULE, rules, koans, scores, pass, quit] >> ",
+                     players[current].name, players[current].stones));
+        match parse_turn(&input) {
+            Ok(Turn::Master(study)) => {
+                println!();
+                let label = master.label(&study);
+                display_labelled(&study, label);
+                koans.push((study, label));
+                turn += 1;
+            },
+            Ok(Turn::Mondo(study)) => {
+                let label = master.label(&study);
+                let mut callers = Vec::new();
+                for offset in 0..players.len() {
+                    let index = (current + offset) % players.len();
+                    if offset > 0 {
+                        clear_screen();
+                    }
+                    println!("\n{}\n({}; total pips: {})",
+                             study, study.to_notation(), study.pip_count());
+                    let call = loop {
+                        let input = read_input(&format!(
+                            "{}, does this study have the property? \
+                             [y/n] >> ", players[index].name));
+                        match input.trim().chars().next() {
+                            Some('Y') | Some('y') => { break Some(true); },
+                            Some('N') | Some('n') => { break Some(false); },
+                            None if input.is_empty() => { break None; },
+                            _ => { wrapln!("Answer y or n."); }
+                        }
+                    };
+                    // (at the end of input, nobody can call, so rather than
+                    // count it against anyone, we end the game)
+                    let call = match call {
+                        Some(call) => call,
+                        None => {
+                            wrapln!("\nThe input ended before {} called \
+                                     the mondo, so the game ends here.",
+                                    players[index].name);
+                            reveal(&master, &players);
+                            return;
+                        }
+                    };
+                    if call == label {
+                        callers.push(index);
+                    }
+                }
+                clear_screen();
+                display_labelled(&study, label);
+                for &index in &callers {
+                    players[index].stones += 1;
+                    players[index].mondo_calls += 1;
+                    wrapln!("{} called it, and earns a stone.",
+                            players[index].name);
+                }
+                if callers.is_empty() {
+                    wrapln!("Nobody called it.");
+                }
+                println!();
+                koans.push((study, label));
+                turn += 1;
+            },
+            Ok(Turn::Guess(guess)) => {
+                if players[current].stones == 0 {
+                    wrapln!("You need a guessing stone to guess; win one \
+                             with a mondo.");
+                    continue;
+                }
+                let guessed = match master.find_rule(&guess) {
+                    Some(guessed) => guessed,
+                    None => {
+                        wrapln!("There's no such criterion to guess; type \
+                                 `rules` to list them.");
+                        continue;
+                    }
+                };
+                players[current].stones -= 1;
+                players[current].guesses += 1;
+                match master.refute(&guessed, rng) {
+                    Some(counterexample) => {
+                        wrapln!("\nNo, that's not the criterion. Here's a \
+                                 counterexample:\n");
+                        let label = master.label(&counterexample);
+                        display_labelled(&counterexample, label);
+                        koans.push((counterexample, label));
+                        turn += 1;
+                    },
+                    None => {
+                        wrapln!("\n{} wins! A study has the property iff {}.",
+                                players[current].name,
+                                master.rule().description());
+                        display_scores(&players);
+                        return;
+                    }
+                }
+            },
+            Ok(Turn::ListRules(words)) => { master.list_rules(&words); },
+            Ok(Turn::ListKoans) => {
+                for &(ref study, label) in &koans {
+                    wrapln!("  {} {}", if label { "yes" } else { "no " },
+                            study.to_notation());
+                }
+            },
+            Ok(Turn::Scores) => { display_scores(&players); },
+            Ok(Turn::Pass) => { turn += 1; },
+            Ok(Turn::Quit) => {
+                reveal(&master, &players);
+                return;
+            },
+            Err(error) => { wrapln!("{}", error); }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concerning_the_turns_of_hot_seat_zendo() {
+        match parse_turn("mondo R3 B1 | Y2\n") {
+            Ok(Turn::Mondo(study)) => {
+                assert_eq!("R3 B1 | Y2", study.to_notation());
+            },
+            _ => panic!("should have understood a mondo")
+        }
+        match parse_turn("master G1\n") {
+            Ok(Turn::Master(study)) => {
+                assert_eq!("G1", study.to_notation());
+            },
+            _ => panic!("should have understood a study for the master")
+        }
+        match parse_turn("guess 12\n") {
+            Ok(Turn::Guess(guess)) => { assert_eq!("12", guess); },
+            _ => panic!("should have understood a guess")
+        }
+        assert!(parse_turn("master P1\n").is_err());
+        assert!(parse_turn("mondo\n").is_err());
+        assert!(parse_turn("R1\n").is_err());
+        match parse_turn("") {
+            Ok(Turn::Quit) => {},
+            _ => panic!("should quit at the end of input")
+        }
+    }
+
+}
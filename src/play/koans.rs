@@ -0,0 +1,98 @@
+use std::fs::File;
+use std::io;
+use std::io::{Read, Write};
+
+use triangles::Study;
+
+
+/// A koan (a study labelled with whether it has the property) is written
+/// `yes` or `no` followed by the study, e.g. `yes R3 B1 | Y2`.
+pub fn parse_koan(line: &str) -> Result<(Study, bool), String> {
+    let line = line.trim();
+    let (label, study) = match line.find(' ') {
+        Some(index) => (&line[..index], line[index+1..].trim()),
+        None => (line, "")
+    };
+    let verdict = match label {
+        "yes" | "y" => true,
+        "no" | "n" => false,
+        _ => {
+            return Err(format!("a koan should start with `yes` or `no`, \
+                                not {:?}", label));
+        }
+    };
+    if study.is_empty() {
+        return Err("a koan needs a study after its label".to_owned());
+    }
+    Ok((Study::from_notation(study)?, verdict))
+}
+
+/// koans, one per line (ignoring blank lines and `#` comments)
+pub fn parse_koans(text: &str) -> Result<Vec<(Study, bool)>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|&(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(number, line)| {
+            parse_koan(line)
+                .map_err(|error| format!("line {}: {}", number + 1, error))
+        })
+        .collect()
+}
+
+pub fn load_koans(path: &str) -> Result<Vec<(Study, bool)>, String> {
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let mut text = String::new();
+    file.read_to_string(&mut text).map_err(|e| e.to_string())?;
+    parse_koans(&text)
+}
+
+/// ask the human for koans until they enter a blank line
+pub fn enter_koans() -> Vec<(Study, bool)> {
+    wrapln!("Enter studies labelled with whether they have the property, \
+             one per line (e.g., `yes R3 B1 | Y2` or `no G1`), and then a \
+             blank line to begin.");
+    let mut koans = Vec::new();
+    loop {
+        print!("koan >> ");
+        io::stdout().flush().expect("couldn't flush stdout?!");
+        let mut input_buffer = String::new();
+        io::stdin()
+            .read_line(&mut input_buffer)
+            .expect("couldn't read stdin!?");
+        if input_buffer.trim().is_empty() {
+            break;
+        }
+        match parse_koan(&input_buffer) {
+            Ok(koan) => { koans.push(koan); },
+            Err(error) => { wrapln!("Couldn't understand that: {}.", error); }
+        }
+    }
+    koans
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concerning_reading_koans() {
+        let koans = parse_koans("# the master's examples\n\
+                                 yes R3 B1 | Y2\n\n\
+                                 no G1\n").unwrap();
+        assert_eq!(vec![(Study::from_notation("R3 B1 | Y2").unwrap(), true),
+                        (Study::from_notation("G1").unwrap(), false)],
+                   koans);
+        assert_eq!(Err("line 2: a koan should start with `yes` or `no`, \
+                        not \"maybe\"".to_owned()),
+                   parse_koans("yes G1\nmaybe G2\n"));
+        assert!(parse_koan("no").is_err());
+        assert_eq!(Err("unknown color 'P' in \"P1\" (expected R, B, G, or \
+                        Y)".to_owned()),
+                   parse_koan("no P1"));
+    }
+
+}
@@ -0,0 +1,264 @@
+use std::io;
+use std::io::Write;
+
+use rand::Rng;
+
+use inference::triangle::{complexity_prior, find_disagreement, Hypothesis,
+                          standard_basic_hypotheses};
+use inference::triangle::hypotheses::JoinedHypothesis;
+use play::triangle::COUNTEREXAMPLE_SAMPLE_CAP;
+use triangles::Study;
+
+
+/// how many hypotheses to list at once when the human asks what rules there
+/// are to guess
+const RULES_LISTED: usize = 20;
+
+/// how many random studies to try when looking for an example with (or
+/// without) the property
+const EXAMPLE_SEARCH_CAP: usize = 10000;
+
+
+/// what the human wants to do next
+enum Move {
+    Propose(Study),
+    ListRules(Vec<String>),
+    Guess(String),
+    GiveUp,
+}
+
+fn parse_move(input: &str) -> Result<Move, String> {
+    // (`read_line` gives us an empty string at the end of input)
+    if input.is_empty() {
+        return Ok(Move::GiveUp);
+    }
+    let input = input.trim();
+    let mut words = input.split_whitespace();
+    if input.split_whitespace().collect::<Vec<_>>() == ["give", "up"] ||
+        input == "quit" {
+        return Ok(Move::GiveUp);
+    }
+    match words.next() {
+        Some("rules") => Ok(Move::ListRules(
+            words.map(|word| word.to_lowercase()).collect())),
+        Some("guess") => {
+            let guess = input["guess".len()..].trim();
+            if guess.is_empty() {
+                Err("Guess what? Name a rule by number or description."
+                    .to_owned())
+            } else {
+                Ok(Move::Guess(guess.to_owned()))
+            }
+        },
+        Some(_) => Study::from_notation(input).map(Move::Propose),
+        None => Err("Propose a study, list rules, or guess.".to_owned())
+    }
+}
+
+
+fn find_example<R: Rng>(rule: &JoinedHypothesis, property: bool,
+                        rng: &mut R) -> Option<Study> {
+    (0..EXAMPLE_SEARCH_CAP)
+        .map(|_| Study::sample(rng))
+        .find(|study| rule.predicts_the_property(study) == property)
+}
+
+pub fn display_labelled(study: &Study, property: bool) {
+    println!("{}\n({}; total pips: {})",
+             study, study.to_notation(), study.pip_count());
+    wrapln!("This study {} the property.\n",
+            if property { "HAS" } else { "does NOT have" });
+}
+
+
+/// the program in the role of Zendo master: keeper of a secret rule, which
+/// it uses to label studies and judge guesses
+pub struct Master {
+    rule: JoinedHypothesis,
+    // (the criteria that can be guessed, in order, so that they can be
+    // referred to by number)
+    hypotheses: Vec<JoinedHypothesis>
+}
+
+impl Master {
+    /// Choose a rule (according to the complexity prior), along with a
+    /// study that has the property and one that doesn't.
+    pub fn new<R: Rng>(rng: &mut R) -> (Self, Study, Study) {
+        let prior = complexity_prior(standard_basic_hypotheses(), rng);
+        let hypotheses = prior.hypotheses().into_iter()
+            .cloned().collect::<Vec<_>>();
+        loop {
+            let rule = prior.sample(rng).expect("prior should be nonempty");
+            let positive_example = find_example(&rule, true, rng);
+            let negative_example = find_example(&rule, false, rng);
+            if let (Some(positive_example), Some(negative_example)) =
+                (positive_example, negative_example) {
+                    let master = Master { rule, hypotheses };
+                    return (master, positive_example, negative_example);
+                }
+        }
+    }
+
+    pub fn rule(&self) -> &JoinedHypothesis {
+        &self.rule
+    }
+
+    pub fn label(&self, study: &Study) -> bool {
+        self.rule.predicts_the_property(study)
+    }
+
+    /// list (some of) the criteria whose descriptions contain all of these
+    /// (lowercase) words
+    pub fn list_rules(&self, words: &[String]) {
+        let matches = self.hypotheses.iter().enumerate()
+            .filter(|&(_, hypothesis)| {
+                let description = hypothesis.description().to_lowercase();
+                words.iter().all(|word| description.contains(word))
+            })
+            .collect::<Vec<_>>();
+        for &(index, hypothesis) in matches.iter().take(RULES_LISTED) {
+            wrapln!("  {}. {}", index, hypothesis.description());
+        }
+        if matches.len() > RULES_LISTED {
+            wrapln!("(and {} more; add words to narrow the search)",
+                    matches.len() - RULES_LISTED);
+        }
+    }
+
+    /// the criterion a guess refers to, by number or description
+    pub fn find_rule(&self, guess: &str) -> Option<JoinedHypothesis> {
+        match guess.parse::<usize>() {
+            Ok(index) => self.hypotheses.get(index).cloned(),
+            Err(_) => self.hypotheses.iter()
+                .find(|hypothesis| {
+                    hypothesis.description().to_lowercase() ==
+                        guess.to_lowercase()
+                })
+                .cloned()
+        }
+    }
+
+    /// a study on which a guessed criterion disagrees with the rule, if the
+    /// guess is wrong
+    pub fn refute<R: Rng>(&self, guessed: &JoinedHypothesis, rng: &mut R)
+                          -> Option<Study> {
+        find_disagreement(guessed, &self.rule, COUNTEREXAMPLE_SAMPLE_CAP, rng)
+    }
+}
+
+
+/// Play the reverse game, in which this program privately chooses a rule
+/// and the human experiments to discover it.
+pub fn play<R: Rng>(rng: &mut R) {
+    wrapln!("Welcome to Mezzanine v. {}! This program has privately chosen \
+             a criterion, and will tell you whether any study you propose \
+             has the property of satisfying it. Propose studies one stack \
+             at a time, separated by `|`, each triangle as the initial of \
+             its color followed by its size, from the ground up (e.g., \
+             `R3 B1 | Y2`). Type `rules` (optionally followed by words to \
+             search for) to list the criteria you can guess, and `guess` \
+             followed by a criterion's number or description to make a \
+             guess.\n",
+             env!("CARGO_PKG_VERSION"));
+
+    let (master, positive_example, negative_example) = Master::new(rng);
+    display_labelled(&positive_example, true);
+    display_labelled(&negative_example, false);
+
+    let mut proposals = 0;
+    let mut guesses = 0;
+    loop {
+        print!("Propose a study, or [rules WORDS.../guess RULE/give up] >> ");
+        io::stdout().flush().expect("couldn't flush stdout?!");
+        let mut input_buffer = String::new();
+        io::stdin()
+            .read_line(&mut input_buffer)
+            .expect("couldn't read stdin!?");
+        match parse_move(&input_buffer) {
+            Ok(Move::Propose(study)) => {
+                proposals += 1;
+                println!();
+                display_labelled(&study, master.label(&study));
+            },
+            Ok(Move::ListRules(words)) => {
+                master.list_rules(&words);
+            },
+            Ok(Move::Guess(guess)) => {
+                let guessed = match master.find_rule(&guess) {
+                    Some(guessed) => guessed,
+                    None => {
+                        wrapln!("There's no such criterion to guess; type \
+                                 `rules` to list them.");
+                        continue;
+                    }
+                };
+                guesses += 1;
+                match master.refute(&guessed, rng) {
+                    Some(counterexample) => {
+                        wrapln!("\nNo, that's not the criterion. Here's a \
+                                 counterexample:\n");
+                        display_labelled(&counterexample,
+                                         master.label(&counterexample));
+                    },
+                    None => {
+                        wrapln!("\nCorrect! A study has the property iff \
+                                 {}. (You proposed {} studies and made {} \
+                                 guesses.)", master.rule().description(),
+                                proposals, guesses);
+                        return;
+                    }
+                }
+            },
+            Ok(Move::GiveUp) => {
+                wrapln!("\nThe criterion was that a study has the property \
+                         iff {}.", master.rule().description());
+                return;
+            },
+            Err(error) => { wrapln!("{}", error); }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concerning_the_moves_of_the_reverse_game() {
+        match parse_move("R3 B1 | Y2\n") {
+            Ok(Move::Propose(study)) => {
+                assert_eq!(6, study.pip_count());
+            },
+            _ => panic!("should have understood a study")
+        }
+        match parse_move("rules Red exactly\n") {
+            Ok(Move::ListRules(words)) => {
+                assert_eq!(vec!["red".to_owned(), "exactly".to_owned()],
+                           words);
+            },
+            _ => panic!("should have understood a search")
+        }
+        match parse_move("guess  the number of pips is even \n") {
+            Ok(Move::Guess(guess)) => {
+                assert_eq!("the number of pips is even", guess);
+            },
+            _ => panic!("should have understood a guess")
+        }
+        assert!(parse_move("guess\n").is_err());
+        assert!(parse_move("R4\n").is_err());
+        match parse_move("") {
+            Ok(Move::GiveUp) => {},
+            _ => panic!("should give up at the end of input")
+        }
+        match parse_move(" give  up\n") {
+            Ok(Move::GiveUp) => {},
+            _ => panic!("should have understood giving up")
+        }
+        // (only in so many words)
+        assert!(parse_move("given R3\n").is_err());
+        assert!(parse_move("give R3\n").is_err());
+        assert!(parse_move("quit now\n").is_err());
+    }
+
+}
@@ -1,40 +1,515 @@
-mod number;
+pub mod card;
+pub mod domain;
+pub mod grid;
+mod hot_seat;
+pub mod koans;
+mod master;
+pub mod number;
+pub mod oracle;
+pub mod rule_history;
+pub mod session;
+pub mod stopping;
+pub mod transcript;
 pub mod triangle;
+pub mod word;
 
 
-use argparse::{ArgumentParser, Store, StoreTrue};
+use std::process;
+use std::time::Duration;
 
+use argparse::{ArgumentParser, Store, StoreOption, StoreTrue};
+use rand::{Rng, SeedableRng, StdRng, thread_rng};
+
+use inference::strategy::strategy_named;
+use inference::prediction_matrix::Lookahead;
+use play::oracle::Secret;
+use play::rule_history::{default_path, load_rule_history, record_rule};
+use play::session::{Game, Session};
+use play::stopping::StoppingPolicy;
+use play::transcript::Transcript;
 use triangles::Study;
 
 
-pub fn decorative_display_header() {
-    let mascot_study = Study::sample();
+/// what the human said in response to a question
+pub enum Response {
+    Verdict(bool),
+    Inspect,
+    /// take back this many answers
+    Rewind(usize),
+    /// stop for now (saving the session, if we're saving)
+    Quit,
+}
+
+pub fn parse_response(input: &str) -> Option<Response> {
+    // (`read_line` gives us an empty string, not even a newline, at the end
+    // of input)
+    if input.is_empty() {
+        return Some(Response::Quit);
+    }
+    let mut words = input.split_whitespace();
+    match words.next() {
+        Some("undo") => Some(Response::Rewind(1)),
+        Some("quit") => Some(Response::Quit),
+        Some("rewind") => words.next()
+            .and_then(|n| n.parse::<usize>().ok())
+            .map(Response::Rewind),
+        _ => match input.trim().chars().next() {
+            Some('Y') | Some('y') => Some(Response::Verdict(true)),
+            Some('N') | Some('n') => Some(Response::Verdict(false)),
+            Some('I') | Some('i') => Some(Response::Inspect),
+            _ => None
+        }
+    }
+}
+
+
+pub fn decorative_display_header<R: Rng>(rng: &mut R) {
+    let mascot_study = Study::sample(rng);
     println!("{}", mascot_study);
 }
 
 
 pub fn play() {
-    let mut bound: u16 = 30;
+    let mut bound: Option<u16> = None;
     let mut number = false;
+    let mut cards = false;
+    let mut words = false;
+    let mut grid = false;
+    let mut seed: Option<usize> = None;
+    let mut error_rate: Option<f64> = None;
+    let mut save_path: Option<String> = None;
+    let mut resume_path: Option<String> = None;
+    let mut transcript_path: Option<String> = None;
+    let mut oracle_secret: Option<String> = None;
+    let mut master = false;
+    let mut koan_path: Option<String> = None;
+    let mut players: Option<String> = None;
+    let mut lookahead_candidates: usize = 0;
+    let mut lookahead_budget: u64 = 2000;
+    let mut strategy_name = "information".to_owned();
+    let mut stopping = StoppingPolicy::default();
+    let mut empirical = false;
+    let mut rule_history_path: Option<String> = None;
     {
         let mut arg_parser = ArgumentParser::new();
         arg_parser.set_description("Mezzanine: a guessing game");
         arg_parser.refer(&mut bound).add_option(
-            &["--bound"], Store,
-            "the largest admissible number in the game"
+            &["--bound"], StoreOption,
+            "the largest admissible number in the game (default 30)"
         );
         // XXX: should really be an enum with a default rather than a boolean
         arg_parser.refer(&mut number).add_option(
             &["--number"], StoreTrue,
             "play the classic number game rather than triangle science"
         );
+        arg_parser.refer(&mut cards).add_option(
+            &["--cards"], StoreTrue,
+            "play a game in the manner of Eleusis, inferring which cards \
+             may follow which, rather than triangle science"
+        );
+        arg_parser.refer(&mut words).add_option(
+            &["--words"], StoreTrue,
+            "play a game about properties of short words rather than \
+             triangle science"
+        );
+        arg_parser.refer(&mut grid).add_option(
+            &["--grid"], StoreTrue,
+            "play a game about points on a small grid rather than triangle \
+             science"
+        );
+        arg_parser.refer(&mut seed).add_option(
+            &["--seed"], StoreOption,
+            "seed for the random number generator (to replay a session)"
+        );
+        arg_parser.refer(&mut error_rate).add_option(
+            &["--error-rate"], StoreOption,
+            "the probability that any given answer is mistaken (default 0: \
+             a single contrary answer rules out a criterion for good)"
+        );
+        arg_parser.refer(&mut save_path).add_option(
+            &["--save"], StoreOption,
+            "file to save the session to on exiting"
+        );
+        arg_parser.refer(&mut resume_path).add_option(
+            &["--resume"], StoreOption,
+            "file to resume a saved session from (and, unless --save says \
+             otherwise, to save it back to)"
+        );
+        arg_parser.refer(&mut transcript_path).add_option(
+            &["--transcript"], StoreOption,
+            "file to write a transcript of the game to on exiting (as HTML \
+             if the name ends in .html, and as Markdown otherwise)"
+        );
+        arg_parser.refer(&mut oracle_secret).add_option(
+            &["--oracle"], StoreOption,
+            "rather than asking a human, answer automatically according to \
+             a secret criterion: \"random\", its index in the hypothesis \
+             space, or its description"
+        );
+        arg_parser.refer(&mut master).add_option(
+            &["--master"], StoreTrue,
+            "play the reverse game: this program chooses a criterion, and \
+             you propose studies to discover it"
+        );
+        arg_parser.refer(&mut koan_path).add_option(
+            &["--koans"], StoreOption,
+            "file of labelled studies to begin the triangle game with, one \
+             per line (e.g., \"yes R3 B1 | Y2\"), or - to type them in"
+        );
+        arg_parser.refer(&mut players).add_option(
+            &["--players"], StoreOption,
+            "play Zendo for several students taking turns, with this \
+             program as the master: their names, separated by commas"
+        );
+        arg_parser.refer(&mut lookahead_candidates).add_option(
+            &["--lookahead"], Store,
+            "choose each question looking two questions ahead, among this \
+             many of the most informative candidates (default 0: choose \
+             greedily)"
+        );
+        arg_parser.refer(&mut lookahead_budget).add_option(
+            &["--lookahead-budget"], Store,
+            "milliseconds to spend looking ahead for each question (default \
+             2000)"
+        );
+        arg_parser.refer(&mut strategy_name).add_option(
+            &["--strategy"], Store,
+            "how to choose questions: \"information\" (greatest expected \
+             reduction in entropy; the default), \"balanced\" (answers as \
+             close to equally likely as possible), \"minimax\" (fewest \
+             criteria left standing whatever the answer), or \"random\""
+        );
+        arg_parser.refer(&mut stopping.confidence).add_option(
+            &["--confidence"], StoreOption,
+            "stop asking once the best guess is at least this probable, and \
+             state it along with the runners-up"
+        );
+        arg_parser.refer(&mut stopping.question_budget).add_option(
+            &["--max-questions"], StoreOption,
+            "stop asking after this many questions, and state the best \
+             guess along with the runners-up"
+        );
+        arg_parser.refer(&mut stopping.entropy_floor).add_option(
+            &["--entropy-floor"], StoreOption,
+            "stop asking once the uncertainty is at most this many bits, \
+             and state the best guess along with the runners-up"
+        );
+        arg_parser.refer(&mut empirical).add_option(
+            &["--empirical-prior"], StoreTrue,
+            "weigh the triangle game's prior towards the kinds of rules \
+             inferred in past sessions (and record the rule this session \
+             infers)"
+        );
+        arg_parser.refer(&mut rule_history_path).add_option(
+            &["--rule-history"], StoreOption,
+            "file in which to record the rule inferred in each completed \
+             triangle game, and from which --empirical-prior learns \
+             (default, with --empirical-prior, ~/.mezzanine-rules; \
+             otherwise, rules aren't recorded)"
+        );
         arg_parser.parse_args_or_exit();
     }
+    let other_games = [number, cards, words, grid];
+    if other_games.iter().filter(|&&chosen| chosen).count() > 1 {
+        println!("Choose just one of the number, card, word, and grid \
+                  games.");
+        process::exit(2);
+    }
+    let triangle_game = !other_games.iter().any(|&chosen| chosen);
+    let chosen_game = [(number, "number"), (cards, "card"), (words, "word"),
+                       (grid, "grid")].iter()
+        .find(|&&(chosen, _)| chosen)
+        .map(|&(_, name)| name);
+    if let Some(bound) = bound {
+        if !number && resume_path.is_none() {
+            println!("The bound is only for the number game.");
+            process::exit(2);
+        }
+        if let Err(error) = (Game::Number { bound }).check() {
+            println!("The number game can't be played so: {}.", error);
+            process::exit(2);
+        }
+    }
+    if let Some(error_rate) = error_rate {
+        if !(0.0..0.5).contains(&error_rate) {
+            println!("The error rate must be at least 0 and less than \
+                      0.5.");
+            process::exit(2);
+        }
+    }
+
+    // (a resumed session carries its own game, seed, error rate, koans, and
+    // prior, and we'd rather refuse than quietly ignore or reapply any given
+    // anew)
+    let resumed = match resume_path {
+        Some(ref path) => match Session::load(path) {
+            Ok(session) => Some(session),
+            Err(error) => {
+                println!("Couldn't resume a session from {}: {}", path, error);
+                process::exit(1);
+            }
+        },
+        None => None
+    };
+    if let Some(ref session) = resumed {
+        if chosen_game.is_some_and(|name| name != session.game.name()) {
+            println!("The session being resumed is of the {} game; resume \
+                      without choosing another.", session.game.name());
+            process::exit(2);
+        }
+        if bound.is_some_and(|bound| session.game != Game::Number { bound }) {
+            println!("The session being resumed isn't of the number game to \
+                      that bound; resume without --bound.");
+            process::exit(2);
+        }
+        if seed.is_some_and(|seed| seed != session.seed) {
+            println!("The session being resumed has the random seed {}; \
+                      resume without --seed, or start anew.", session.seed);
+            process::exit(2);
+        }
+        if error_rate.is_some_and(|rate| rate != session.error_rate) {
+            println!("The session being resumed has an error rate of {}; \
+                      resume without --error-rate, or start anew.",
+                     session.error_rate);
+            process::exit(2);
+        }
+        if koan_path.is_some() {
+            println!("The session being resumed already began with its \
+                      koans; resume without --koans.");
+            process::exit(2);
+        }
+        if empirical && session.past_rules.is_none() {
+            println!("The session being resumed didn't begin with the \
+                      empirical prior; resume without --empirical-prior, or \
+                      start anew.");
+            process::exit(2);
+        }
+    }
+    let bound = bound.unwrap_or(number::DEFAULT_BOUND);
+    let error_rate = resumed.as_ref()
+        .map_or(error_rate.unwrap_or(0.), |session| session.error_rate);
+    let empirical = resumed.as_ref()
+        .map_or(empirical, |session| session.past_rules.is_some());
+
+    if let Some(confidence) = stopping.confidence {
+        if confidence <= 0. || confidence > 1. {
+            println!("The confidence to stop at must be greater than 0 and \
+                      at most 1.");
+            process::exit(2);
+        }
+    }
+    let stopping_early = stopping.confidence.is_some() ||
+        stopping.question_budget.is_some() ||
+        stopping.entropy_floor.is_some();
+    if !triangle_game && (stopping_early || empirical) {
+        println!("Stopping early and the empirical prior are only for the \
+                  triangle game.");
+        process::exit(2);
+    }
 
-    decorative_display_header();
-    if number {
-        number::play(bound);
+    // (we only keep a history if asked to, whether by naming where or by
+    // wanting to learn from it)
+    let rule_history_path = if empirical {
+        rule_history_path.or_else(default_path)
+    } else {
+        rule_history_path
+    };
+    let saved_rules = resumed.as_ref()
+        .and_then(|session| session.past_rules.clone());
+    let rule_history = if let Some(rules) = saved_rules {
+        // (a resumed game learns from just the rules it learned from when
+        // it began, however the history has grown since)
+        Some(rules)
+    } else if empirical {
+        let path = match rule_history_path {
+            Some(ref path) => path,
+            None => {
+                println!("Couldn't tell where the rule history is; say \
+                          where with --rule-history.");
+                process::exit(2);
+            }
+        };
+        match load_rule_history(path) {
+            Ok(rules) => Some(rules),
+            Err(error) => {
+                println!("Couldn't read the rule history from {}: {}",
+                         path, error);
+                process::exit(1);
+            }
+        }
     } else {
-        triangle::play();
+        None
+    };
+    let rule_history = rule_history.as_ref().map(|rules| &rules[..]);
+
+    let strategy = match strategy_named(&strategy_name) {
+        Ok(strategy) => strategy,
+        Err(error) => {
+            println!("{}", error);
+            process::exit(2);
+        }
+    };
+    if lookahead_candidates > 0 && !strategy.seeks_information() {
+        println!("Looking ahead is only for strategies after the greatest \
+                  expected information, not {:?}.", strategy.name());
+        process::exit(2);
+    }
+
+    let lookahead = if lookahead_candidates > 0 {
+        Some(Lookahead { candidates: lookahead_candidates,
+                         time_budget: Duration::from_millis(lookahead_budget) })
+    } else {
+        None
+    };
+    if !triangle_game && lookahead.is_some() {
+        println!("Looking ahead is only for the triangle game.");
+        process::exit(2);
+    }
+    let tactics = triangle::Tactics { strategy: &*strategy,
+                                      lookahead,
+                                      stopping: &stopping,
+                                      rule_history };
+
+    if let Some(players) = players {
+        let names = players.split(',')
+            .map(|name| name.trim().to_owned())
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            println!("Name at least one player.");
+            process::exit(2);
+        }
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+        let seed_slice: &[_] = &[seed];
+        let mut rng: StdRng = SeedableRng::from_seed(seed_slice);
+        decorative_display_header(&mut rng);
+        println!("Random seed: {}", seed);
+        hot_seat::play(&mut rng, names);
+        return;
+    }
+
+    if master {
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+        let seed_slice: &[_] = &[seed];
+        let mut rng: StdRng = SeedableRng::from_seed(seed_slice);
+        decorative_display_header(&mut rng);
+        println!("Random seed: {}", seed);
+        master::play(&mut rng);
+        return;
+    }
+
+    if let Some(secret) = oracle_secret {
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+        let seed_slice: &[_] = &[seed];
+        let mut rng: StdRng = SeedableRng::from_seed(seed_slice);
+        println!("Random seed: {}", seed);
+        let secret = Secret::parse(&secret);
+        let report = if number {
+            number::play_oracle(bound, error_rate, &secret, &*strategy,
+                                &mut rng)
+        } else if cards {
+            card::play_oracle(error_rate, &secret, &*strategy, &mut rng)
+        } else if words {
+            word::play_oracle(error_rate, &secret, &*strategy, &mut rng)
+        } else if grid {
+            grid::play_oracle(grid::GRID_SIZE, error_rate, &secret,
+                              &*strategy, &mut rng)
+        } else {
+            triangle::play_oracle(triangle::STUDY_POOL_SIZE, error_rate,
+                                  &secret, &tactics, &mut rng)
+        };
+        match report {
+            Ok(report) => { report.display(); },
+            Err(error) => {
+                println!("Couldn't set up the oracle: {}", error);
+                process::exit(2);
+            }
+        }
+        return;
+    }
+
+    let mut session = match resumed {
+        Some(session) => session,
+        None => {
+            let game = if number {
+                Game::Number { bound }
+            } else if cards {
+                Game::Card
+            } else if words {
+                Game::Word
+            } else if grid {
+                Game::Grid { size: grid::GRID_SIZE }
+            } else {
+                Game::Triangle { pool_size: triangle::STUDY_POOL_SIZE }
+            };
+            let seed = seed.unwrap_or_else(|| thread_rng().gen());
+            let mut session = Session::new(game, seed, error_rate);
+            session.past_rules = rule_history.map(|rules| rules.to_vec());
+            session
+        }
+    };
+    let save_path = save_path.or(resume_path);
+
+    let seed_slice: &[_] = &[session.seed];
+    let mut rng: StdRng = SeedableRng::from_seed(seed_slice);
+
+    decorative_display_header(&mut rng);
+    println!("Random seed: {}", session.seed);
+    let mut transcript = Transcript::new(
+        format!("Mezzanine v. {} (random seed {})",
+                env!("CARGO_PKG_VERSION"), session.seed));
+    let triangle_game = matches!(session.game, Game::Triangle { .. });
+    if !triangle_game && (koan_path.is_some() || lookahead.is_some() ||
+                          stopping_early || empirical) {
+        println!("Koans, looking ahead, stopping early, and the empirical \
+                  prior are only for the triangle game.");
+        process::exit(2);
+    }
+    match session.game {
+        Game::Number { bound } => {
+            number::play(&mut rng, bound, &mut session, &mut transcript,
+                         &*strategy);
+        },
+        Game::Card => {
+            card::play(&mut rng, &mut session, &mut transcript, &*strategy);
+        },
+        Game::Word => {
+            word::play(&mut rng, &mut session, &mut transcript, &*strategy);
+        },
+        Game::Grid { size } => {
+            grid::play(&mut rng, size, &mut session, &mut transcript,
+                       &*strategy);
+        },
+        Game::Triangle { pool_size } => {
+            let inference = triangle::play(
+                &mut rng, pool_size, &mut session, &mut transcript,
+                koan_path.as_ref().map(|path| &path[..]), &tactics);
+            if let (Some(inference), Some(path)) =
+                (inference, rule_history_path.as_ref()) {
+                    if let Err(error) = record_rule(path, &inference) {
+                        println!("Couldn't record the inferred rule in {}: \
+                                  {}", path, error);
+                    }
+                }
+        }
+    }
+
+    if let Some(path) = transcript_path {
+        match transcript.export(&path) {
+            Ok(()) => { println!("Transcript written to {}.", path); },
+            Err(error) => {
+                println!("Couldn't write the transcript to {}: {}",
+                         path, error);
+            }
+        }
+    }
+
+    if let Some(path) = save_path {
+        match session.save(&path) {
+            Ok(()) => { println!("Session saved to {}.", path); },
+            Err(error) => {
+                println!("Couldn't save the session to {}: {}", path, error);
+            }
+        }
     }
 }
@@ -1,143 +1,139 @@
-use std::collections::HashSet;
-use std::io;
-use std::io::Write;
+use inference::number::{divisibility_eliminated, sieve, NumberDomain,
+                        Study};
+use inference::strategy::Strategy;
+use rand::Rng;
 
-use inference::number::{BoundednessHypothesis, Distribution,
-                        DivisibilityHypothesis,
-                        Hypothesis, JoinedHypothesis};
+use play::domain;
+use play::domain::Presentation;
+use play::oracle::{OracleReport, Secret};
+use play::session::Session;
+use play::transcript::Transcript;
 
 
-pub fn play(mut bound: u16) {
-    wrapln!("Welcome to Mezzanine v. {}! Privately think of a criterion \
-             concerning natural numbers not greater than {}. \
-             This program will attempt to efficiently infer the nature of \
-             the criterion by asking you whether specific numbers do or do \
-             not have the property of satisfying the criterion.",
-             env!("CARGO_PKG_VERSION"), bound);
+/// the largest admissible number, unless the player says otherwise
+pub const DEFAULT_BOUND: u16 = 30;
 
-    bound += 1; // convenience with exclusive ranges
-    let studies = (1..bound).collect::<Vec<_>>();
+/// the largest bound we'll play to (the conjunctions and disjunctions of
+/// divisibility and boundedness grow as the cube of the bound)
+pub const MAX_BOUND: u16 = 200;
 
-    let mut hypotheses = Vec::new();
 
-    // divisibility
-    for divisor in 1..bound/2 {
-        hypotheses.push(JoinedHypothesis::full_stop(
-            DivisibilityHypothesis::new(divisor).to_basic()));
+impl Presentation for NumberDomain {
+    fn welcome(&self) -> String {
+        format!("Welcome to Mezzanine v. {}! Privately think of a criterion \
+                 concerning natural numbers not greater than {}. This \
+                 program will attempt to efficiently infer the nature of the \
+                 criterion by asking you whether specific numbers do or do \
+                 not have the property of satisfying the criterion.",
+                env!("CARGO_PKG_VERSION"), self.bound)
     }
 
-    // boundedness
-    for min in 2..bound-1 {
-        for max in min..bound {
-            hypotheses.push(
-                JoinedHypothesis::full_stop(
-                    BoundednessHypothesis::new(min, max).to_basic()))
-        }
+    fn subject(&self) -> &'static str {
+        "a natural number has the property"
     }
 
-    // conjunctions and disjunctions of divisibility and boundedness
-    //
-    // This approach is a little uglier for users than actually reasoning about
-    // ranges (it'll choose the first encountered of allowable bounds for a
-    // prediction, rather than the tightest bound), but it's more generalizable
-    let mut joined_predictions = HashSet::new();
-    for divisor in 1..bound/2 {
-        for min in 2..bound-1 {
-            for max in min..bound {
-                let multiples = (1..bound)
-                    .map(|i| i*divisor)
-                    .take_while(|&n| n <= bound)
-                    .collect::<HashSet<_>>();
-                let range = (min..max+1).collect::<HashSet<_>>();
-
-                let mut conjunctive_prediction = multiples.intersection(&range)
-                    .cloned().collect::<Vec<_>>();
-                conjunctive_prediction.sort();
-                let mut disjunctive_prediction = multiples.union(&range)
-                    .cloned().collect::<Vec<_>>();
-                disjunctive_prediction.sort();
-
-                let mut factor_prediction = multiples.iter()
-                    .cloned().collect::<Vec<_>>();
-                factor_prediction.sort();
-                let mut range_prediction = range.iter()
-                    .cloned().collect::<Vec<_>>();
-                range_prediction.sort();
-
-                if conjunctive_prediction != factor_prediction &&
-                        conjunctive_prediction != range_prediction &&
-                        !joined_predictions.contains(&conjunctive_prediction) {
-                    hypotheses.push(
-                        JoinedHypothesis::and(
-                            DivisibilityHypothesis::new(divisor).to_basic(),
-                            BoundednessHypothesis::new(min, max).to_basic())
-                    );
-                    joined_predictions.insert(conjunctive_prediction);
-                }
-                if disjunctive_prediction != factor_prediction &&
-                        disjunctive_prediction != range_prediction &&
-                        !joined_predictions.contains(&disjunctive_prediction) {
-                    hypotheses.push(
-                        JoinedHypothesis::or(
-                            DivisibilityHypothesis::new(divisor).to_basic(),
-                            BoundednessHypothesis::new(min, max).to_basic())
-                    );
-                    joined_predictions.insert(disjunctive_prediction);
-                }
-            }
-        }
+    fn encode(&self, study: &Study) -> String {
+        study.to_string()
     }
 
-    let mut beliefs = Distribution::ignorance_prior(hypotheses);
-    println!("Size of hypothesis space: {}", beliefs.len());
-
-    loop {
-        let complete_certainty = beliefs.completely_certain();
-        match complete_certainty {
-            None => {
-                let study = beliefs.burning_question(studies.clone()).unwrap();
-                let voi = beliefs.value_of_information(study);
-                if voi == 0.0 {
-                    wrapln!("This program has inferred all that it can, and \
-                              is indifferent between the following hypotheses \
-                              concerning when a number has the property:");
-                    for hypothesis in beliefs.hypotheses() {
-                        println!("  * {}", hypothesis.description());
-                    }
-                    break;
-                }
-                wrapln!("This program's belief distribution (over {} remaining \
-                         hypotheses) has an entropy of {:.3} bits. Learning \
-                         whether {} has the property is expected to reduce the \
-                         entropy by {:.3} bits.",
-                         beliefs.len(), beliefs.entropy(), study, voi);
-                let mut verdict_maybe = None;
-                while let None = verdict_maybe {
-                    print!("Does {} have the property? [Y/n] >> ", study);
-                    io::stdout().flush().expect("couldn't flush stdout?!");
-                    let mut input_buffer = String::new();
-                    io::stdin()
-                        .read_line(&mut input_buffer)
-                        .ok().expect("couldn't read stdin!?");
-                    verdict_maybe = match input_buffer.chars().nth(0) {
-                        Some('Y') | Some('y') => Some(true),
-                        Some('N') | Some('n') => Some(false),
-                        _ => {
-                            wrapln!("\nAnswer Y or n. You must comply.");
-                            continue;
-                        },
-                    };
-                }
-                let verdict = verdict_maybe.unwrap();
-                wrapln!("On the question of whether {} has the property, \
-                         you said {}.\n", study, verdict);
-                beliefs = beliefs.updated(study, verdict);
-            }
-            Some(known_truth) => {
-                wrapln!("This program infers that a natural number has the \
-                         property iff {}.", known_truth.description());
-                return;
-            }
+    fn decode(&self, encoded: &str) -> Result<Study, String> {
+        match encoded.trim().parse::<Study>() {
+            Ok(study) if study >= 1 && study <= self.bound => Ok(study),
+            _ => Err(format!("{:?} isn't a number from 1 to {}",
+                             encoded, self.bound))
         }
     }
+
+    fn proposition(&self, study: &Study) -> String {
+        format!("{} has the property", study)
+    }
+
+    fn question(&self, study: &Study) -> String {
+        format!("Does {} have the property?", study)
+    }
+
+    fn render(&self, study: &Study) -> String {
+        study.to_string()
+    }
+
+    fn remark(&self, study: &Study, verdict: bool) -> Option<String> {
+        explain_divisibility(*study, verdict, self.bound)
+    }
+}
+
+
+pub fn play<R: Rng>(rng: &mut R, bound: u16, session: &mut Session,
+                    transcript: &mut Transcript, strategy: &dyn Strategy) {
+    domain::play(&NumberDomain { bound }, rng, session, transcript,
+                 strategy);
+}
+
+
+/// Play a game against an automated oracle that answers according to a
+/// secret hypothesis (mistakenly, with probability `error_rate`).
+pub fn play_oracle<R: Rng>(bound: u16, error_rate: f64, secret: &Secret,
+                           strategy: &dyn Strategy, rng: &mut R)
+                           -> Result<OracleReport, String> {
+    domain::play_oracle(&NumberDomain { bound }, error_rate, secret,
+                        strategy, rng)
+}
+
+
+/// "1, 2, and 3"
+fn enumerate_in_prose(items: &[u16]) -> String {
+    let items = items.iter().map(|item| item.to_string())
+        .collect::<Vec<_>>();
+    match items.len() {
+        0 => String::new(),
+        1 => items[0].clone(),
+        2 => format!("{} and {}", items[0], items[1]),
+        n => format!("{}, and {}", items[..n-1].join(", "), items[n-1])
+    }
+}
+
+
+/// Which of the divisibility criteria (for numbers not greater than `bound`)
+/// an answer about `study` counts against, in terms of its factorization
+/// (or `None`, if it doesn't count against any).
+pub fn explain_divisibility(study: Study, verdict: bool, bound: u16)
+                            -> Option<String> {
+    let eliminated = divisibility_eliminated(study, verdict, bound);
+    if eliminated.is_empty() {
+        return None;
+    }
+    let factors = sieve().prime_factors(study);
+    let factorization = match factors.len() {
+        0 => format!("{} has no prime factors", study),
+        1 => format!("{} is prime", study),
+        _ => format!("{} = {}", study, factors.iter()
+                     .map(|factor| factor.to_string())
+                     .collect::<Vec<_>>().join(" × "))
+    };
+    Some(format!("Since {}, that counts against divisibility by {}.",
+                 factorization, enumerate_in_prose(&eliminated)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concerning_explaining_divisibility() {
+        assert_eq!(Some("Since 12 = 2 × 2 × 3, that counts against \
+                         divisibility by 1, 2, 3, 4, 6, and 12.".to_owned()),
+                   explain_divisibility(12, false, 30));
+        assert_eq!(Some("Since 7 is prime, that counts against \
+                         divisibility by 2, 3, 4, 5, 6, 8, 9, 10, 11, 12, \
+                         13, and 14.".to_owned()),
+                   explain_divisibility(7, true, 30));
+        // (only divisibility by 1 through 9 is entertained within 20)
+        assert_eq!(Some("Since 6 = 2 × 3, that counts against \
+                         divisibility by 4, 5, 7, 8, and 9.".to_owned()),
+                   explain_divisibility(6, true, 20));
+        // (the only criterion within 3 is divisibility by 1, which every
+        // number satisfies)
+        assert_eq!(None, explain_divisibility(2, true, 3));
+    }
+
 }
@@ -0,0 +1,181 @@
+use rand::Rng;
+
+
+/// which hypothesis an automated oracle should answer according to
+#[derive(Debug, PartialEq, Clone)]
+pub enum Secret {
+    /// the hypothesis at this position in the (sorted) hypothesis space
+    Index(usize),
+    /// the hypothesis with this description (ignoring case)
+    Text(String),
+    Random,
+}
+
+impl Secret {
+    /// `random`, an index, or a description
+    pub fn parse(spec: &str) -> Self {
+        let spec = spec.trim();
+        if spec == "random" {
+            Secret::Random
+        } else if let Ok(index) = spec.parse::<usize>() {
+            Secret::Index(index)
+        } else {
+            Secret::Text(spec.to_owned())
+        }
+    }
+
+    pub fn choose<H: Copy, F, R: Rng>(&self, hypotheses: &[H], describe: F,
+                                      rng: &mut R) -> Result<H, String>
+        where F: Fn(&H) -> String {
+        match *self {
+            Secret::Index(index) => hypotheses.get(index).cloned()
+                .ok_or_else(|| format!(
+                    "there's no hypothesis #{} (only {} of them)",
+                    index, hypotheses.len())),
+            Secret::Text(ref text) => {
+                let text = text.to_lowercase();
+                hypotheses.iter()
+                    .find(|hypothesis| {
+                        describe(hypothesis).to_lowercase() == text
+                    })
+                    .cloned()
+                    .ok_or_else(|| format!(
+                        "there's no hypothesis described as {:?}", text))
+            },
+            Secret::Random => {
+                if hypotheses.is_empty() {
+                    return Err("there are no hypotheses to choose from"
+                               .to_owned());
+                }
+                Ok(hypotheses[rng.gen_range(0, hypotheses.len())])
+            }
+        }
+    }
+}
+
+
+/// how a game against an automated oracle went
+#[derive(Debug, Clone)]
+pub struct OracleReport {
+    pub secret: String,
+    pub inference: String,
+    // (the probability the engine assigned to its inference at the end)
+    pub confidence: f64,
+    pub questions: usize,
+    /// whether the inference agrees with the secret on every study it was
+    /// checked against
+    pub extensionally_equal: bool,
+    /// the entropy of the engine's beliefs before each question, and after
+    /// the last
+    pub entropy_trajectory: Vec<f64>,
+}
+
+impl OracleReport {
+    pub fn display(&self) {
+        wrapln!("Secret criterion: {}", self.secret);
+        wrapln!("Inferred criterion (with probability {:.3}, after {} \
+                 questions): {}",
+                self.confidence, self.questions, self.inference);
+        wrapln!("The inference is {}extensionally equal to the secret.",
+                if self.extensionally_equal { "" } else { "NOT " });
+        wrapln!("Entropy trajectory (bits): {}",
+                self.entropy_trajectory.iter()
+                    .map(|entropy| format!("{:.3}", entropy.abs()))
+                    .collect::<Vec<_>>()
+                    .join(" → "));
+    }
+}
+
+
+/// what the oracle says about a study that the secret says `truth` about:
+/// usually the truth, but mistaken with probability `error_rate`
+pub fn answer<R: Rng>(truth: bool, error_rate: f64, rng: &mut R) -> bool {
+    if error_rate > 0. && rng.gen::<f64>() < error_rate {
+        !truth
+    } else {
+        truth
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, StdRng};
+    use inference::strategy::{strategy_named, ExpectedInformation,
+                              STRATEGY_NAMES};
+    use play::{card, grid, number, word};
+
+    #[test]
+    fn concerning_choosing_secrets() {
+        let seed: &[_] = &[1729];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let hypotheses = [2, 3, 5];
+        let describe = |n: &u16| format!("divisible by {}", n);
+        assert_eq!(Secret::Index(1), Secret::parse("1"));
+        assert_eq!(Ok(3), Secret::parse("1")
+                   .choose(&hypotheses, describe, &mut rng));
+        assert_eq!(Ok(5), Secret::parse("Divisible by 5")
+                   .choose(&hypotheses, describe, &mut rng));
+        assert!(Secret::parse("divisible by 7")
+                .choose(&hypotheses, describe, &mut rng).is_err());
+        assert!(Secret::Index(3)
+                .choose(&hypotheses, describe, &mut rng).is_err());
+        let random = Secret::parse("random")
+            .choose(&hypotheses, describe, &mut rng);
+        assert!(hypotheses.contains(&random.unwrap()));
+    }
+
+    #[test]
+    fn concerning_an_oracle_for_the_number_game() {
+        let seed: &[_] = &[1729];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let secret = Secret::parse("it is divisible by 3");
+        for name in &STRATEGY_NAMES {
+            let strategy = strategy_named(name).unwrap();
+            let report = number::play_oracle(20, 0., &secret, &*strategy,
+                                             &mut rng).unwrap();
+            assert!(report.extensionally_equal);
+            assert_eq!(report.questions + 1, report.entropy_trajectory.len());
+            // (but it need not be entirely certain, as some hypotheses can't
+            // be told apart within the bound)
+            assert!(report.entropy_trajectory[report.questions] <= 1.);
+        }
+    }
+
+    #[test]
+    fn concerning_an_oracle_for_the_card_game() {
+        let seed: &[_] = &[1729];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let secret = Secret::parse(
+            "it is of the other color from the previous card or it is \
+             higher in rank than the previous card");
+        let report = card::play_oracle(0., &secret, &ExpectedInformation,
+                                       &mut rng).unwrap();
+        assert!(report.extensionally_equal);
+        assert_eq!(0., report.entropy_trajectory[report.questions]);
+    }
+
+    #[test]
+    fn concerning_an_oracle_for_the_word_game() {
+        let seed: &[_] = &[1729];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let secret = Secret::parse("it ends with 'e' or it starts with 's'");
+        let report = word::play_oracle(0., &secret, &ExpectedInformation,
+                                       &mut rng).unwrap();
+        assert!(report.extensionally_equal);
+    }
+
+    #[test]
+    fn concerning_an_oracle_for_the_grid_game() {
+        let seed: &[_] = &[1729];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+        let secret = Secret::parse("x + y is even and it is within 5 of the \
+                                    origin");
+        let report = grid::play_oracle(grid::GRID_SIZE, 0.1, &secret,
+                                       &ExpectedInformation, &mut rng)
+            .unwrap();
+        assert!(report.extensionally_equal);
+    }
+
+}
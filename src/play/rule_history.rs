@@ -0,0 +1,62 @@
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
+use std::path::Path;
+
+
+/// where to keep the rules inferred in past sessions, unless told otherwise
+pub fn default_path() -> Option<String> {
+    env::var("HOME").ok().map(|home| {
+        Path::new(&home).join(".mezzanine-rules")
+            .to_string_lossy().into_owned()
+    })
+}
+
+/// the descriptions of past sessions' rules, one per line (ignoring blank
+/// lines and `#` comments)
+pub fn parse_rule_history(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_owned())
+        .collect()
+}
+
+/// the rules inferred in past sessions (none, if there's no history yet)
+pub fn load_rule_history(path: &str) -> Result<Vec<String>, String> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(ref error) if error.kind() == ErrorKind::NotFound => {
+            return Ok(Vec::new());
+        },
+        Err(error) => { return Err(error.to_string()); }
+    };
+    let mut text = String::new();
+    file.read_to_string(&mut text).map_err(|e| e.to_string())?;
+    Ok(parse_rule_history(&text))
+}
+
+pub fn record_rule(path: &str, description: &str) -> Result<(), String> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)
+        .map_err(|e| e.to_string())?;
+    writeln!(file, "{}", description).map_err(|e| e.to_string())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concerning_reading_the_rule_history() {
+        assert_eq!(vec!["the number of Red triangles is not less than 1"
+                        .to_owned(),
+                        "the number of pips is even".to_owned()],
+                   parse_rule_history(
+                       "# rules inferred in past sessions\n\
+                        the number of Red triangles is not less than 1\n\n  \
+                        the number of pips is even  \n"));
+        assert!(parse_rule_history("").is_empty());
+    }
+
+}
@@ -0,0 +1,290 @@
+use std::fs::File;
+use std::io::{Read, Write};
+
+use play::grid::MAX_GRID_SIZE;
+use play::number::MAX_BOUND;
+use play::triangle::MAX_STUDY_POOL_SIZE;
+
+
+const SESSION_HEADER: &str = "mezzanine-session 1";
+
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Game {
+    Triangle { pool_size: usize },
+    Number { bound: u16 },
+    Card,
+    Word,
+    Grid { size: u16 },
+}
+
+impl Game {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Game::Triangle { .. } => "triangle",
+            Game::Number { .. } => "number",
+            Game::Card => "card",
+            Game::Word => "word",
+            Game::Grid { .. } => "grid"
+        }
+    }
+
+    /// whether the game can be played as configured (a session file could
+    /// say anything)
+    pub fn check(&self) -> Result<(), String> {
+        match *self {
+            Game::Triangle { pool_size }
+                if pool_size < 1 || pool_size > MAX_STUDY_POOL_SIZE => {
+                    Err(format!("pool-size {} is outside 1 to {}",
+                                pool_size, MAX_STUDY_POOL_SIZE))
+                },
+            Game::Number { bound } if bound < 2 || bound > MAX_BOUND => {
+                Err(format!("bound {} is outside 2 to {}",
+                            bound, MAX_BOUND))
+            },
+            Game::Grid { size } if size < 2 || size > MAX_GRID_SIZE => {
+                Err(format!("size {} is outside 2 to {}",
+                            size, MAX_GRID_SIZE))
+            },
+            _ => Ok(())
+        }
+    }
+}
+
+
+/// Everything needed to pick a game back up where it was left off: the
+/// configuration that determines the hypothesis space, the seed that
+/// determines everything random about it, the past rules its prior was
+/// learned from (if it was), any koans the game began with, and the answers
+/// given so far.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Session {
+    pub game: Game,
+    pub seed: usize,
+    pub error_rate: f64,
+    // (the descriptions of the rules inferred in past sessions, as they
+    // stood when the game began, since the rule history grows)
+    pub past_rules: Option<Vec<String>>,
+    // (each koan, in the same form as the answers)
+    pub koans: Vec<(String, bool)>,
+    // (each study asked about, in its textual form—`Study::to_notation`
+    // for triangles—and the answer)
+    pub answers: Vec<(String, bool)>
+}
+
+impl Session {
+    pub fn new(game: Game, seed: usize, error_rate: f64) -> Self {
+        Session { game, seed, error_rate, past_rules: None,
+                  koans: Vec::new(), answers: Vec::new() }
+    }
+
+    pub fn record_koan(&mut self, study: String, verdict: bool) {
+        self.koans.push((study, verdict));
+    }
+
+    pub fn record(&mut self, study: String, verdict: bool) {
+        self.answers.push((study, verdict));
+    }
+
+    /// forget all but the first `count` answers
+    pub fn truncate(&mut self, count: usize) {
+        self.answers.truncate(count);
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![SESSION_HEADER.to_owned(),
+                             format!("game {}", self.game.name())];
+        match self.game {
+            Game::Triangle { pool_size } => {
+                lines.push(format!("pool-size {}", pool_size));
+            },
+            Game::Number { bound } => {
+                lines.push(format!("bound {}", bound));
+            },
+            Game::Grid { size } => {
+                lines.push(format!("size {}", size));
+            },
+            Game::Card | Game::Word => {}
+        }
+        lines.push(format!("seed {}", self.seed));
+        lines.push(format!("error-rate {}", self.error_rate));
+        if let Some(ref past_rules) = self.past_rules {
+            lines.push("empirical-prior".to_owned());
+            for rule in past_rules {
+                lines.push(format!("past-rule {}", rule));
+            }
+        }
+        for &(ref study, verdict) in &self.koans {
+            lines.push(format!("koan {} {}",
+                               if verdict { "yes" } else { "no" }, study));
+        }
+        for &(ref study, verdict) in &self.answers {
+            lines.push(format!("answer {} {}",
+                               if verdict { "yes" } else { "no" }, study));
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        if lines.next().map(|line| line.trim()) != Some(SESSION_HEADER) {
+            return Err("not a Mezzanine session file".to_owned());
+        }
+
+        let mut game_name = None;
+        let mut pool_size = None;
+        let mut bound = None;
+        let mut size = None;
+        let mut seed = None;
+        let mut error_rate = None;
+        let mut empirical_prior = false;
+        let mut past_rules = Vec::new();
+        let mut koans = Vec::new();
+        let mut answers = Vec::new();
+        for line in lines {
+            let line = line.trim();
+            let (key, value) = match line.find(' ') {
+                Some(index) => (&line[..index], line[index+1..].trim()),
+                None => (line, "")
+            };
+            match key {
+                "game" => { game_name = Some(value.to_owned()); },
+                "pool-size" => {
+                    pool_size = Some(parse_field(key, value)?);
+                },
+                "bound" => { bound = Some(parse_field(key, value)?); },
+                "size" => { size = Some(parse_field(key, value)?); },
+                "seed" => { seed = Some(parse_field(key, value)?); },
+                "error-rate" => {
+                    error_rate = Some(parse_field(key, value)?);
+                },
+                "empirical-prior" => { empirical_prior = true; },
+                "past-rule" => { past_rules.push(value.to_owned()); },
+                "koan" => { koans.push(parse_labelled(key, value)?); },
+                "answer" => { answers.push(parse_labelled(key, value)?); },
+                _ => {
+                    return Err(format!("unknown session field {:?}", key));
+                }
+            }
+        }
+
+        let game = match game_name.as_ref().map(|name| &name[..]) {
+            Some("triangle") => Game::Triangle {
+                pool_size: pool_size.ok_or("missing pool-size")? },
+            Some("number") => Game::Number {
+                bound: bound.ok_or("missing bound")? },
+            Some("card") => Game::Card,
+            Some("word") => Game::Word,
+            Some("grid") => Game::Grid {
+                size: size.ok_or("missing size")? },
+            Some(other) => { return Err(format!("unknown game {:?}", other)); },
+            None => { return Err("missing game".to_owned()); }
+        };
+        game.check()?;
+        let error_rate = error_rate.ok_or("missing error-rate")?;
+        if !empirical_prior && !past_rules.is_empty() {
+            return Err("past rules without an empirical prior".to_owned());
+        }
+        if error_rate < 0. || error_rate >= 0.5 {
+            return Err(format!("error-rate {} is outside 0 to 0.5",
+                               error_rate));
+        }
+        Ok(Session { game,
+                     seed: seed.ok_or("missing seed")?,
+                     error_rate,
+                     past_rules: if empirical_prior {
+                         Some(past_rules)
+                     } else {
+                         None
+                     },
+                     koans, answers })
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let mut file = File::create(path).map_err(|e| e.to_string())?;
+        file.write_all(self.to_text().as_bytes()).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let mut file = File::open(path).map_err(|e| e.to_string())?;
+        let mut text = String::new();
+        file.read_to_string(&mut text).map_err(|e| e.to_string())?;
+        Session::from_text(&text)
+    }
+}
+
+
+/// a study's textual form labelled `yes` or `no`
+fn parse_labelled(key: &str, value: &str) -> Result<(String, bool), String> {
+    let (verdict, study) = match value.find(' ') {
+        Some(index) => (&value[..index], value[index+1..].trim()),
+        None => (value, "")
+    };
+    let verdict = match verdict {
+        "yes" => true,
+        "no" => false,
+        _ => {
+            return Err(format!("couldn't understand {} {:?}", key, verdict));
+        }
+    };
+    Ok((study.to_owned(), verdict))
+}
+
+
+fn parse_field<T: ::std::str::FromStr>(key: &str, value: &str)
+                                      -> Result<T, String> {
+    value.parse::<T>()
+        .map_err(|_| format!("couldn't understand {} {:?}", key, value))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use triangles::Study;
+
+    #[test]
+    fn concerning_the_round_trip_through_text() {
+        let mut session = Session::new(Game::Triangle { pool_size: 500 },
+                                       1729, 0.05);
+        let study = Study::from_notation("R3 B1 | Y2").unwrap();
+        session.past_rules = Some(vec![
+            "the number of Red triangles is not less than 2".to_owned()]);
+        session.record_koan("B1".to_owned(), false);
+        session.record(study.to_notation(), true);
+        session.record("G1".to_owned(), false);
+        assert_eq!(Ok(session.clone()), Session::from_text(&session.to_text()));
+
+        let number_session = Session::new(Game::Number { bound: 30 }, 3, 0.);
+        assert_eq!(Ok(number_session.clone()),
+                   Session::from_text(&number_session.to_text()));
+
+        let grid_session = Session::new(Game::Grid { size: 8 }, 4, 0.1);
+        assert_eq!(Ok(grid_session.clone()),
+                   Session::from_text(&grid_session.to_text()));
+
+        let mut card_session = Session::new(Game::Card, 5, 0.);
+        card_session.record("QH 10S".to_owned(), false);
+        assert_eq!(Ok(card_session.clone()),
+                   Session::from_text(&card_session.to_text()));
+    }
+
+    #[test]
+    fn concerning_unreadable_sessions() {
+        assert!(Session::from_text("hello").is_err());
+        assert!(Session::from_text("mezzanine-session 1\ngame chess\n\
+                                    seed 1\nerror-rate 0\n").is_err());
+        // (nor will we try to play games that can't be played)
+        for game in &["game grid\nsize 0", "game grid\nsize 300",
+                      "game triangle\npool-size 0",
+                      "game number\nbound 0", "game number\nbound 65535"] {
+            assert!(Session::from_text(&format!(
+                "mezzanine-session 1\n{}\nseed 1\nerror-rate 0\n", game))
+                    .is_err());
+        }
+        assert!(Session::from_text("mezzanine-session 1\ngame card\nseed 1\n\
+                                    error-rate 0.7\n").is_err());
+    }
+
+}
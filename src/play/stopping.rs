@@ -0,0 +1,64 @@
+/// When to stop asking questions and settle for a best guess, short of
+/// having nothing left worth asking (each criterion is optional).
+#[derive(Debug, Default, Copy, Clone)]
+pub struct StoppingPolicy {
+    /// stop once the best guess is at least this probable
+    pub confidence: Option<f64>,
+    /// stop after this many questions
+    pub question_budget: Option<usize>,
+    /// stop once the entropy of our beliefs is at most this many bits
+    pub entropy_floor: Option<f64>,
+}
+
+impl StoppingPolicy {
+    /// why we should stop (as a clause to follow "because"), if we should
+    pub fn reason_to_stop(&self, questions: usize, confidence: f64,
+                          entropy: f64) -> Option<String> {
+        if let Some(threshold) = self.confidence {
+            if confidence >= threshold {
+                return Some(format!("its best guess has a probability of at \
+                                     least {}", threshold));
+            }
+        }
+        if let Some(budget) = self.question_budget {
+            if questions >= budget {
+                return Some(format!("it has used up its budget of {} \
+                                     questions", budget));
+            }
+        }
+        if let Some(floor) = self.entropy_floor {
+            if entropy <= floor {
+                return Some(format!("its uncertainty has fallen to {:.3} \
+                                     bits, at or below the floor of {}",
+                                    entropy.abs(), floor));
+            }
+        }
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concerning_when_to_stop() {
+        assert_eq!(None, StoppingPolicy::default()
+                   .reason_to_stop(1000, 0.999, 0.01));
+        let policy = StoppingPolicy { confidence: Some(0.95),
+                                      question_budget: Some(20),
+                                      entropy_floor: Some(0.5) };
+        assert_eq!(None, policy.reason_to_stop(19, 0.9, 0.6));
+        assert_eq!(Some("its best guess has a probability of at least \
+                         0.95".to_owned()),
+                   policy.reason_to_stop(3, 0.96, 0.3));
+        assert_eq!(Some("it has used up its budget of 20 questions"
+                        .to_owned()),
+                   policy.reason_to_stop(20, 0.5, 2.));
+        assert_eq!(Some("its uncertainty has fallen to 0.400 bits, at or \
+                         below the floor of 0.5".to_owned()),
+                   policy.reason_to_stop(4, 0.9, 0.4));
+    }
+
+}
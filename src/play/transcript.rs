@@ -0,0 +1,180 @@
+use std::fs::File;
+use std::io::Write;
+
+use display::{ansi_to_html, strip_ansi};
+
+
+/// one question and the answer it got
+#[derive(Debug, Clone)]
+pub struct Exchange {
+    // as displayed in the terminal, ANSI colors and all
+    pub rendered_study: String,
+    pub hypothesis_count: usize,
+    pub entropy: f64,
+    pub value_of_information: f64,
+    pub verdict: bool
+}
+
+
+/// A record of a game for writing up afterwards: what was asked, what we
+/// expected to learn from it, what we were told, and what we concluded.
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    title: String,
+    // (the koans the game began with, as if they were questions)
+    koans: Vec<Exchange>,
+    exchanges: Vec<Exchange>,
+    // a summary, followed by the hypotheses it mentions (if any)
+    conclusion: Option<(String, Vec<String>)>
+}
+
+impl Transcript {
+    pub fn new(title: String) -> Self {
+        Transcript { title, koans: Vec::new(), exchanges: Vec::new(),
+                     conclusion: None }
+    }
+
+    pub fn record_koan(&mut self, exchange: Exchange) {
+        self.koans.push(exchange);
+    }
+
+    pub fn record(&mut self, exchange: Exchange) {
+        self.exchanges.push(exchange);
+    }
+
+    /// forget all but the first `count` exchanges
+    pub fn truncate(&mut self, count: usize) {
+        self.exchanges.truncate(count);
+    }
+
+    pub fn conclude(&mut self, summary: String, hypotheses: Vec<String>) {
+        self.conclusion = Some((summary, hypotheses));
+    }
+
+    fn describe_beliefs(exchange: &Exchange) -> String {
+        format!("The belief distribution (over {} remaining hypotheses) had \
+                 an entropy of {:.3} bits; the answer was expected to reduce \
+                 it by {:.3} bits.",
+                exchange.hypothesis_count, exchange.entropy,
+                exchange.value_of_information)
+    }
+
+    fn describe_verdict(exchange: &Exchange) -> &'static str {
+        if exchange.verdict { "yes" } else { "no" }
+    }
+
+    /// the koans and then the questions, each with its heading
+    fn sections(&self) -> Vec<(String, &Exchange)> {
+        let koans = self.koans.iter().enumerate()
+            .map(|(i, koan)| (format!("Koan {}", i + 1), koan));
+        let questions = self.exchanges.iter().enumerate()
+            .map(|(i, exchange)| (format!("Question {}", i + 1), exchange));
+        koans.chain(questions).collect()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# {}\n\n", self.title);
+        for (heading, exchange) in self.sections() {
+            markdown.push_str(&format!("## {}\n\n", heading));
+            markdown.push_str(&Transcript::describe_beliefs(exchange));
+            markdown.push_str("\n\n```\n");
+            markdown.push_str(&strip_ansi(&exchange.rendered_study));
+            markdown.push_str("\n```\n\n");
+            markdown.push_str(&format!(
+                "Has the property? **{}**\n\n",
+                Transcript::describe_verdict(exchange)));
+        }
+        if let Some((ref summary, ref hypotheses)) = self.conclusion {
+            markdown.push_str("## Conclusion\n\n");
+            markdown.push_str(summary);
+            markdown.push_str("\n\n");
+            for hypothesis in hypotheses {
+                markdown.push_str(&format!(" * {}\n", hypothesis));
+            }
+        }
+        markdown
+    }
+
+    pub fn to_html(&self) -> String {
+        let escape = |text: &str| ansi_to_html(text);
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{0}</title>\n<style>\n\
+             body {{ font-family: sans-serif; max-width: 50em; \
+             margin: auto; }}\n\
+             pre.study {{ background: #1c1c1c; color: #e8e8e8; \
+             padding: 1em; line-height: 1.15; display: inline-block; }}\n\
+             </style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+            escape(&self.title));
+        for (heading, exchange) in self.sections() {
+            html.push_str(&format!("<h2>{}</h2>\n", heading));
+            html.push_str(&format!(
+                "<p>{}</p>\n",
+                escape(&Transcript::describe_beliefs(exchange))));
+            html.push_str(&format!("<pre class=\"study\">{}</pre>\n",
+                                   escape(&exchange.rendered_study)));
+            html.push_str(&format!(
+                "<p>Has the property? <strong>{}</strong></p>\n",
+                Transcript::describe_verdict(exchange)));
+        }
+        if let Some((ref summary, ref hypotheses)) = self.conclusion {
+            html.push_str("<h2>Conclusion</h2>\n");
+            html.push_str(&format!("<p>{}</p>\n", escape(summary)));
+            if !hypotheses.is_empty() {
+                html.push_str("<ul>\n");
+                for hypothesis in hypotheses {
+                    html.push_str(&format!("<li>{}</li>\n",
+                                           escape(hypothesis)));
+                }
+                html.push_str("</ul>\n");
+            }
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// write the transcript as HTML if the path says so (ending in `.html`
+    /// or `.htm`), and as Markdown otherwise
+    pub fn export(&self, path: &str) -> Result<(), String> {
+        let lowercase_path = path.to_lowercase();
+        let text = if lowercase_path.ends_with(".html") ||
+            lowercase_path.ends_with(".htm") {
+                self.to_html()
+            } else {
+                self.to_markdown()
+            };
+        let mut file = File::create(path).map_err(|e| e.to_string())?;
+        file.write_all(text.as_bytes()).map_err(|e| e.to_string())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use triangles::{Color, Size, Stack, Study, Triangle};
+
+    #[test]
+    fn concerning_the_colors_of_exported_studies() {
+        let study = study!(stack!(Triangle::new(Color::Red, Size::One)));
+        let mut transcript = Transcript::new("A game".to_owned());
+        transcript.record(Exchange {
+            rendered_study: format!("{}", study),
+            hypothesis_count: 2,
+            entropy: 1.,
+            value_of_information: 1.,
+            verdict: true
+        });
+        transcript.conclude("It's red.".to_owned(), Vec::new());
+
+        let markdown = transcript.to_markdown();
+        assert!(markdown.contains("```\n/̣\\ \n‾‾ \n```"));
+        assert!(!markdown.contains('\u{1b}'));
+        assert!(markdown.contains("## Conclusion\n\nIt's red."));
+
+        let html = transcript.to_html();
+        assert!(html.contains("<span style=\"color: #e03c31\">/̣\\ </span>"));
+        assert!(!html.contains('\u{1b}'));
+    }
+
+}
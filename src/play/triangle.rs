@@ -1,36 +1,323 @@
 use std::io;
 use std::io::Write;
+use std::process;
 
-use inference::triangle::{complexity_prior, Hypothesis,
-                          standard_basic_hypotheses};
+use rand::Rng;
 
+use inference::distribution::Domain;
+use inference::prediction_matrix::{Lookahead, PredictionMatrix};
+use inference::strategy::Strategy;
+use inference::triangle::{complexity_prior, empirical_prior,
+                          find_disagreement, Distribution, Hypothesis,
+                          LOCAL_SEARCH_STEPS, standard_basic_hypotheses,
+                          TriangleDomain};
+use inference::triangle::hypotheses::JoinedHypothesis;
+use play::{parse_response, Response};
+use play::domain::INCONSISTENT_ANSWER;
+use play::koans::{enter_koans, load_koans};
+use play::oracle::{answer, OracleReport, Secret};
+use play::session::Session;
+use play::stopping::StoppingPolicy;
+use play::transcript::{Exchange, Transcript};
+use triangles::Study;
 
-pub fn play() {
+
+/// how many candidate studies to consider asking about over the course of
+/// a game
+pub const STUDY_POOL_SIZE: usize = 10000;
+
+/// the most candidate studies we'll pool (a session could ask for any number)
+pub const MAX_STUDY_POOL_SIZE: usize = 100000;
+
+/// how many random studies to try (after exhausting the small ones) when
+/// looking for a counterexample to a hypothesis
+pub const COUNTEREXAMPLE_SAMPLE_CAP: usize = 10000;
+
+/// how many of the runners-up to this program's best guess to name when it
+/// settles for the guess under a stopping policy
+const RUNNERS_UP_NAMED: usize = 3;
+
+/// how many of the runners-up to this program's best guess to look for
+/// counterexamples in favor of, when there's nothing left in the pool worth
+/// asking about
+const REFUTATION_RIVALS: usize = 5;
+
+
+/// how the triangle game chooses its questions, when it stops asking, and
+/// what past sessions it learns its prior from
+pub struct Tactics<'a> {
+    pub strategy: &'a dyn Strategy,
+    pub lookahead: Option<Lookahead>,
+    pub stopping: &'a StoppingPolicy,
+    pub rule_history: Option<&'a [String]>
+}
+
+
+/// whether two hypotheses agree on the given studies, and we can't find a
+/// counterexample elsewhere
+pub fn extensionally_equal<H, R>(one: &H, another: &H, studies: &[Study],
+                                 rng: &mut R) -> bool
+    where H: Hypothesis<Study>, R: Rng {
+    studies.iter().all(|study| {
+        one.predicts_the_property(study) ==
+            another.predicts_the_property(study)
+    }) && find_disagreement(one, another, COUNTEREXAMPLE_SAMPLE_CAP,
+                            rng).is_none()
+}
+
+
+/// A study that would refute our best guess in favor of one of its rivals,
+/// if there's one worth asking about (in which case we also return the
+/// guess and the rival).
+fn refutation<H, R: Rng>(beliefs: &Distribution<H>, rng: &mut R)
+                         -> Option<(H, H, Study)>
+    where H: Hypothesis<Study> + Ord + Copy {
+    let (best_guess, _) = match beliefs.best_guess() {
+        Some(guess) => guess,
+        None => { return None; }
+    };
+    let mut rivals = beliefs.hypotheses().into_iter()
+        .cloned()
+        .filter(|&hypothesis| hypothesis != best_guess)
+        .collect::<Vec<_>>();
+    // (most probable first, keeping their order otherwise)
+    rivals.sort_by(|&a, &b| {
+        beliefs.belief(b).partial_cmp(&beliefs.belief(a))
+            .expect("probabilities should be comparable")
+    });
+    for rival in rivals.into_iter().take(REFUTATION_RIVALS) {
+        if let Some(counterexample) = find_disagreement(
+            &best_guess, &rival, COUNTEREXAMPLE_SAMPLE_CAP, rng) {
+                let value = beliefs.value_of_information(&counterexample);
+                if beliefs.worth_asking(value) {
+                    return Some((best_guess, rival, counterexample));
+                }
+            }
+    }
+    None
+}
+
+
+/// the complexity prior over the standard hypotheses (blended with the
+/// rules, by description, inferred in past sessions, if we're to learn from
+/// them), along with its predictions for a pool of candidate studies
+fn initial_beliefs<R: Rng>(rng: &mut R, pool_size: usize, error_rate: f64,
+                           rule_history: Option<&[String]>)
+                           -> (Distribution<JoinedHypothesis>,
+                               PredictionMatrix<JoinedHypothesis, Study>) {
+    let basic_hypotheses = standard_basic_hypotheses();
+    let mut prior = complexity_prior(basic_hypotheses, rng);
+    if let Some(descriptions) = rule_history {
+        let past_rules = descriptions.iter()
+            .filter_map(|description| {
+                let description = description.to_lowercase();
+                prior.hypotheses().into_iter()
+                    .find(|hypothesis| {
+                        hypothesis.description().to_lowercase() == description
+                    })
+                    .cloned()
+            })
+            .collect::<Vec<_>>();
+        wrapln!("Learning from {} rule(s) inferred in past sessions.",
+                past_rules.len());
+        prior = empirical_prior(&prior, &past_rules);
+    }
+    let prior = prior.with_error_rate(error_rate);
+    let hypotheses = prior.hypotheses().into_iter()
+        .cloned().collect::<Vec<_>>();
+    let matrix = PredictionMatrix::new(
+        hypotheses, TriangleDomain.candidate_studies(pool_size, rng));
+    (prior, matrix)
+}
+
+
+/// the study to ask about next: if we're after information (and not
+/// looking ahead), the best we can find by searching locally around the
+/// best of the pool, and otherwise the pool study of the strategy's choice
+/// (or the one that looks best two questions ahead)
+fn choose_question<H, R: Rng>(beliefs: &Distribution<H>,
+                              matrix: &PredictionMatrix<H, Study>,
+                              strategy: &dyn Strategy,
+                              lookahead: Option<&Lookahead>, rng: &mut R)
+                              -> Study
+    where H: Hypothesis<Study> + Ord + Copy + Send + Sync {
+    let question = match lookahead {
+        Some(lookahead) => {
+            beliefs.burning_question_looking_ahead(matrix, lookahead)
+        },
+        None if strategy.seeks_information() => {
+            return beliefs.burning_question_near(matrix, LOCAL_SEARCH_STEPS,
+                                                 rng)
+                .expect("study pool should be nonempty");
+        },
+        None => beliefs.burning_question_in(matrix, strategy, rng)
+    }.expect("study pool should be nonempty");
+    matrix.studies()[question].clone()
+}
+
+
+/// `koan_path` names a file of koans to begin with, or is `-` if the human
+/// is to type them in. Returns the description of the rule this program
+/// settles on, if it settles on one.
+pub fn play<R: Rng>(rng: &mut R, pool_size: usize, session: &mut Session,
+                    transcript: &mut Transcript, koan_path: Option<&str>,
+                    tactics: &Tactics) -> Option<String> {
+    let Tactics { strategy, lookahead, stopping, rule_history } = *tactics;
     wrapln!("Welcome to Mezzanine v. {}! Privately think of a criterion. \
              This program will attempt to efficiently infer the nature of \
              the criterion by asking you whether specific studies do or do \
              not have the property of satisfying the criterion.",
              env!("CARGO_PKG_VERSION"));
 
-    let basic_hypotheses = standard_basic_hypotheses();
-    let mut beliefs = complexity_prior(basic_hypotheses);
+    // (a resumed session brings its koans with it)
+    let saved_koans = session.koans.drain(..).collect::<Vec<_>>();
+    let koans = if !saved_koans.is_empty() {
+        saved_koans.into_iter()
+            .filter_map(|(encoded_study, verdict)| {
+                match Study::from_notation(&encoded_study) {
+                    Ok(study) => Some((study, verdict)),
+                    Err(_) => {
+                        wrapln!("The saved koan {:?} couldn't be read; \
+                                 leaving it out.", encoded_study);
+                        None
+                    }
+                }
+            })
+            .collect()
+    } else {
+        match koan_path {
+            Some("-") => enter_koans(),
+            Some(path) => match load_koans(path) {
+                Ok(koans) => koans,
+                Err(error) => {
+                    println!("Couldn't read koans from {}: {}", path, error);
+                    process::exit(1);
+                }
+            },
+            None => Vec::new()
+        }
+    };
+
+    let error_rate = session.error_rate;
+    let (mut beliefs, matrix) = initial_beliefs(rng, pool_size, error_rate,
+                                                rule_history);
     println!("Size of hypothesis space: {}", beliefs.len());
 
     let initial_entropy = beliefs.entropy();
-    let mut question_count = 0;
+    // (koans aren't questions: they don't count against any budget of
+    // questions, and can't be taken back)
+    if !koans.is_empty() {
+        for (study, verdict) in koans {
+            let updated_beliefs = beliefs.updated(&study, verdict);
+            if updated_beliefs.len() == 0 {
+                wrapln!("No criterion this program knows of is consistent \
+                         with the koan `{} {}` (and those before it); \
+                         ignoring it.",
+                        if verdict { "yes" } else { "no" },
+                        study.to_notation());
+                continue;
+            }
+            session.record_koan(study.to_notation(), verdict);
+            transcript.record_koan(exchange(&beliefs, &study, verdict));
+            beliefs = updated_beliefs;
+        }
+        wrapln!("The koans removed {:.3} bits of uncertainty (from {:.3} to \
+                 {:.3}), leaving {} hypotheses.\n",
+                initial_entropy - beliefs.entropy(),
+                initial_entropy, beliefs.entropy(), beliefs.len());
+    }
 
-    loop {
-        let study = beliefs.burning_question(0.95, 10000);
-        let value_of_continuing = beliefs.value_of_information(&study);
-        if value_of_continuing == 0.0 {
+    // (each study asked about, the answer, and what we believed before
+    // hearing it)
+    let mut history = Vec::new();
+    let mut reasking = None;
+
+    for &(ref encoded_study, verdict) in &session.answers {
+        match Study::from_notation(encoded_study) {
+            Ok(study) => {
+                let updated_beliefs = beliefs.updated(&study, verdict);
+                if updated_beliefs.len() == 0 {
+                    wrapln!("The saved answer about {:?} is inconsistent \
+                             with those before it; resuming from just \
+                             before it.", encoded_study);
+                    break;
+                }
+                transcript.record(exchange(&beliefs, &study, verdict));
+                history.push((study, verdict, beliefs));
+                beliefs = updated_beliefs;
+            },
+            Err(_) => {
+                wrapln!("The saved study {:?} couldn't be read; resuming \
+                         from just before it.", encoded_study);
+                break;
+            }
+        }
+    }
+    session.truncate(history.len());
+    if !history.is_empty() {
+        wrapln!("Resuming after {} answers.\n", history.len());
+    }
+
+    'questioning: loop {
+        if beliefs.len() > 1 {
+            let (_, confidence) = beliefs.best_guess()
+                .expect("should have a best guess");
+            if let Some(reason) = stopping.reason_to_stop(
+                history.len(), confidence, beliefs.entropy()) {
+                wrapln!("After asking {} questions (from an initial state \
+                         of {:.3}-bit uncertainty), this program stops, \
+                         because {}.\n",
+                        history.len(), initial_entropy, reason);
+                return Some(settle(&beliefs, transcript));
+            }
+        }
+        let mut study = match reasking.take() {
+            Some(study) => study,
+            None => choose_question(&beliefs, &matrix, strategy,
+                                    lookahead.as_ref(), rng)
+        };
+        let mut value_of_continuing = beliefs.value_of_information(&study);
+        if !beliefs.worth_asking(value_of_continuing) {
+            // Nothing in the pool distinguishes our best guess from its
+            // rivals, but something outside it might.
+            if let Some((best_guess, rival, counterexample)) =
+                refutation(&beliefs, rng) {
+                    wrapln!("This program's best guess is that a study has \
+                             the property iff {}, but the study below would \
+                             refute that in favor of the hypothesis that \
+                             {}.\n",
+                            best_guess.description(), rival.description());
+                    value_of_continuing =
+                        beliefs.value_of_information(&counterexample);
+                    study = counterexample;
+                }
+        }
+        if !beliefs.worth_asking(value_of_continuing) {
             wrapln!("After asking {} questions (from an initial state \
                      of {:.3}-bit uncertainty),\n",
-                    question_count, initial_entropy);
+                    history.len(), initial_entropy);
 
             if beliefs.len() == 1 {
+                let inference = beliefs.hypotheses()[0].description();
                 wrapln!("this program infers that a study has the \
-                         property iff {}.",
-                        beliefs.hypotheses()[0].description());
+                         property iff {}.", inference);
+                transcript.conclude(
+                    format!("This program infers that a study has the \
+                             property iff {}.", inference),
+                    Vec::new());
+                return Some(inference);
+            } else if error_rate > 0. {
+                let (best_guess, probability) = beliefs.best_guess()
+                    .expect("should have a best guess");
+                wrapln!("this program's best guess (with probability \
+                         {:.3}) is that a study has the property iff {}.",
+                        probability, best_guess.description());
+                transcript.conclude(
+                    format!("This program's best guess (with probability \
+                             {:.3}) is that a study has the property iff {}.",
+                            probability, best_guess.description()),
+                    Vec::new());
+                return Some(best_guess.description());
             } else {
                 wrapln!("this program has inferred all that it can, and \
                          is indifferent between the following hypotheses \
@@ -38,8 +325,15 @@ pub fn play() {
                 for hypothesis in beliefs.hypotheses() {
                     println!("  * {}", hypothesis.description());
                 }
+                transcript.conclude(
+                    "This program inferred all that it could, and was \
+                     indifferent between the following hypotheses \
+                     concerning when a study has the property:".to_owned(),
+                    beliefs.hypotheses().iter()
+                        .map(|hypothesis| hypothesis.description())
+                        .collect());
+                return None;
             }
-            break;
         }
         wrapln!("This program's belief distribution (over {} remaining \
                  hypotheses) has an entropy of {:.3} bits. Learning \
@@ -48,23 +342,44 @@ pub fn play() {
                 beliefs.len(), beliefs.entropy(), value_of_continuing);
         let mut verdict_maybe = None;
         while let None = verdict_maybe {
-            question_count += 1;
-            print!("Does the study below have the property? [Y/n/inspect]\n\
-                    {}\n(total pips: {})\n>> ",
-                   study, study.pip_count());
+            print!("Does the study below have the property? \
+                    [Y/n/inspect/undo/rewind N/quit]\n{}\n\
+                    ({}; total pips: {})\n>> ",
+                   study, study.to_notation(), study.pip_count());
             io::stdout().flush().expect("couldn't flush stdout?!");
             let mut input_buffer = String::new();
             io::stdin()
                 .read_line(&mut input_buffer)
                 .ok().expect("couldn't read stdin!?");
-            verdict_maybe = match input_buffer.chars().nth(0) {
-                Some('Y') | Some('y') => Some(true),
-                Some('N') | Some('n') => Some(false),
-                Some('I') | Some('i') => {
+            verdict_maybe = match parse_response(&input_buffer) {
+                Some(Response::Verdict(verdict)) => Some(verdict),
+                Some(Response::Inspect) => {
                     beliefs.inspect(20);
                     continue;
-                }
-                _ => {
+                },
+                Some(Response::Rewind(steps)) => {
+                    if steps == 0 || steps > history.len() {
+                        wrapln!("\nCan't take back {} answers when you've \
+                                 only given {}.", steps, history.len());
+                        continue;
+                    }
+                    let rewind_to = history.len() - steps;
+                    let (earlier_study, _, earlier_beliefs) =
+                        history.drain(rewind_to..).next()
+                        .expect("should have history to rewind");
+                    wrapln!("\nTaking back {} answer(s). Asking question {} \
+                             again.\n", steps, rewind_to + 1);
+                    session.truncate(rewind_to);
+                    transcript.truncate(rewind_to);
+                    beliefs = earlier_beliefs;
+                    reasking = Some(earlier_study);
+                    continue 'questioning;
+                },
+                Some(Response::Quit) => {
+                    wrapln!("\nStopping after {} answers.", history.len());
+                    return None;
+                },
+                None => {
                     wrapln!("\nAnswer Y or n. You must comply.");
                     continue;
                 },
@@ -74,6 +389,102 @@ pub fn play() {
         let verdict = verdict_maybe.unwrap();
         wrapln!("On the question of whether the previous study had the \
                  property, you said {}.\n", verdict);
-        beliefs = beliefs.updated(&study, verdict);
+        let updated_beliefs = beliefs.updated(&study, verdict);
+        if updated_beliefs.len() == 0 {
+            wrapln!("{}\n", INCONSISTENT_ANSWER);
+            reasking = Some(study);
+            continue;
+        }
+        session.record(study.to_notation(), verdict);
+        transcript.record(exchange(&beliefs, &study, verdict));
+        history.push((study, verdict, beliefs));
+        beliefs = updated_beliefs;
     }
 }
+
+
+/// state our best guess, its probability, and the runners-up (returning the
+/// best guess's description)
+fn settle<H>(beliefs: &Distribution<H>, transcript: &mut Transcript)
+             -> String where H: Hypothesis<Study> + Ord + Copy {
+    let ranked = beliefs.most_probable(RUNNERS_UP_NAMED + 1);
+    let (best_guess, probability) = ranked[0];
+    let summary = format!("This program's best guess (with probability \
+                           {:.3}) is that a study has the property iff {}.",
+                          probability, best_guess.description());
+    wrapln!("{} The runners-up:", summary);
+    let runners_up = ranked[1..].iter()
+        .map(|&(hypothesis, probability)| {
+            format!("{} ({:.3})", hypothesis.description(), probability)
+        })
+        .collect::<Vec<_>>();
+    for runner_up in &runners_up {
+        wrapln!("  * {}", runner_up);
+    }
+    transcript.conclude(format!("{} The runners-up:", summary), runners_up);
+    best_guess.description()
+}
+
+
+fn exchange<H>(beliefs: &Distribution<H>, study: &Study, verdict: bool)
+               -> Exchange where H: Hypothesis<Study> + Ord + Copy {
+    Exchange {
+        rendered_study: format!("{}\n({}; total pips: {})",
+                                study, study.to_notation(),
+                                study.pip_count()),
+        hypothesis_count: beliefs.len(),
+        entropy: beliefs.entropy(),
+        value_of_information: beliefs.value_of_information(study),
+        verdict
+    }
+}
+
+
+/// Play a game against an automated oracle that answers according to a
+/// secret hypothesis (mistakenly, with probability `error_rate`).
+pub fn play_oracle<R: Rng>(pool_size: usize, error_rate: f64, secret: &Secret,
+                           tactics: &Tactics, rng: &mut R)
+                           -> Result<OracleReport, String> {
+    let Tactics { strategy, lookahead, stopping, rule_history } = *tactics;
+    let (mut beliefs, matrix) = initial_beliefs(rng, pool_size, error_rate,
+                                                rule_history);
+    let secret = secret.choose(matrix.hypotheses(),
+                               |hypothesis| hypothesis.description(), rng)?;
+
+    let mut entropy_trajectory = vec![beliefs.entropy()];
+    loop {
+        let (_, confidence) = beliefs.best_guess()
+            .expect("should have a best guess");
+        if stopping.reason_to_stop(entropy_trajectory.len() - 1, confidence,
+                                   beliefs.entropy()).is_some() {
+            break;
+        }
+        let question = choose_question(&beliefs, &matrix, strategy,
+                                       lookahead.as_ref(), rng);
+        let study = if beliefs.worth_asking(
+            beliefs.value_of_information(&question)) {
+            question
+        } else {
+            match refutation(&beliefs, rng) {
+                Some((_, _, counterexample)) => counterexample,
+                None => { break; }
+            }
+        };
+        let truth = secret.predicts_the_property(&study);
+        beliefs = beliefs.updated(&study, answer(truth, error_rate, rng));
+        entropy_trajectory.push(beliefs.entropy());
+    }
+
+    let (inference, confidence) = beliefs.best_guess()
+        .expect("should have a best guess");
+    let extensionally_equal = extensionally_equal(
+        &inference, &secret, matrix.studies(), rng);
+    Ok(OracleReport {
+        secret: secret.description(),
+        inference: inference.description(),
+        confidence,
+        questions: entropy_trajectory.len() - 1,
+        extensionally_equal,
+        entropy_trajectory
+    })
+}
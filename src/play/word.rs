@@ -0,0 +1,68 @@
+use inference::strategy::Strategy;
+use inference::word::{Study, WordDomain};
+use rand::Rng;
+
+use play::domain;
+use play::domain::Presentation;
+use play::oracle::{OracleReport, Secret};
+use play::session::Session;
+use play::transcript::Transcript;
+
+
+impl Presentation for WordDomain {
+    fn welcome(&self) -> String {
+        format!("Welcome to Mezzanine v. {}! Privately think of a criterion \
+                 concerning words—how long they are, how they start or end, \
+                 their vowels, or the order of their letters. This program \
+                 will attempt to efficiently infer the nature of the \
+                 criterion by asking you whether specific words (among {} \
+                 familiar ones) do or do not have the property of \
+                 satisfying it.",
+                env!("CARGO_PKG_VERSION"), self.words.len())
+    }
+
+    fn subject(&self) -> &'static str {
+        "a word has the property"
+    }
+
+    fn encode(&self, study: &Study) -> String {
+        study.clone()
+    }
+
+    fn decode(&self, encoded: &str) -> Result<Study, String> {
+        let word = encoded.trim().to_lowercase();
+        if self.words.contains(&word) {
+            Ok(word)
+        } else {
+            Err(format!("{:?} isn't in the word list", encoded))
+        }
+    }
+
+    fn proposition(&self, study: &Study) -> String {
+        format!("\"{}\" has the property", study)
+    }
+
+    fn question(&self, study: &Study) -> String {
+        format!("Does \"{}\" have the property?", study)
+    }
+
+    fn render(&self, study: &Study) -> String {
+        study.clone()
+    }
+}
+
+
+pub fn play<R: Rng>(rng: &mut R, session: &mut Session,
+                    transcript: &mut Transcript, strategy: &dyn Strategy) {
+    domain::play(&WordDomain::bundled(), rng, session, transcript, strategy);
+}
+
+
+/// Play a game against an automated oracle that answers according to a
+/// secret criterion (mistakenly, with probability `error_rate`).
+pub fn play_oracle<R: Rng>(error_rate: f64, secret: &Secret,
+                           strategy: &dyn Strategy, rng: &mut R)
+                           -> Result<OracleReport, String> {
+    domain::play_oracle(&WordDomain::bundled(), error_rate, secret, strategy,
+                        rng)
+}
@@ -3,7 +3,7 @@ use std::slice;
 
 use ansi_term;
 use display;
-use rand::random;
+use rand::Rng;
 
 /// We will classify our gloss'ry of shapes into compliance
 /// We are magical methodical apes doing triangle science
@@ -16,7 +16,7 @@ const TWO_FORM: &'static str = " /\\  \n/..\\ \n‾‾‾‾ ";
 const THREE_FORM: &'static str = "  /\\   \n /  \\  \n/ ...\\ \n‾‾‾‾‾‾ ";
 
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
 pub enum Size {
     One,
     Two,
@@ -47,13 +47,13 @@ impl Size {
         SIZES.iter()
     }
 
-    pub fn sample() -> Self {
-        let index = random::<usize>() % 3;
+    pub fn sample<R: Rng>(rng: &mut R) -> Self {
+        let index = rng.gen_range(0, 3);
         SIZES[index]
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
 pub enum Color {
     Red,
     Blue,
@@ -78,8 +78,8 @@ impl Color {
         COLORS.iter()
     }
 
-    pub fn sample() -> Self {
-        let index = random::<usize>() % 4;
+    pub fn sample<R: Rng>(rng: &mut R) -> Self {
+        let index = rng.gen_range(0, 4);
         COLORS[index]
     }
 }
@@ -96,12 +96,54 @@ impl Triangle {
         Triangle { color: color, size: size }
     }
 
-    pub fn sample() -> Self {
-        Triangle::new(Color::sample(), Size::sample())
+    pub fn sample<R: Rng>(rng: &mut R) -> Self {
+        let color = Color::sample(rng);
+        Triangle::new(color, Size::sample(rng))
     }
 }
 
 
+impl Triangle {
+    fn to_notation(self) -> String {
+        let initial = match self.color {
+            Color::Red => 'R',
+            Color::Blue => 'B',
+            Color::Green => 'G',
+            Color::Yellow => 'Y'
+        };
+        format!("{}{}", initial, self.size.pips())
+    }
+
+    fn from_notation(token: &str) -> Result<Self, String> {
+        let mut chars = token.chars();
+        let color = match chars.next() {
+            Some('R') | Some('r') => Color::Red,
+            Some('B') | Some('b') => Color::Blue,
+            Some('G') | Some('g') => Color::Green,
+            Some('Y') | Some('y') => Color::Yellow,
+            Some(other) => {
+                return Err(format!("unknown color {:?} in {:?} (expected \
+                                    R, B, G, or Y)", other, token));
+            },
+            None => { return Err("expected a triangle".to_owned()); }
+        };
+        let size = match chars.as_str() {
+            "1" => Size::One,
+            "2" => Size::Two,
+            "3" => Size::Three,
+            "" => {
+                return Err(format!("missing size in {:?} (expected 1, 2, or \
+                                    3 after the color)", token));
+            },
+            other => {
+                return Err(format!("unknown size {:?} in {:?} (expected 1, \
+                                    2, or 3)", other, token));
+            }
+        };
+        Ok(Triangle::new(color, size))
+    }
+}
+
 impl fmt::Display for Triangle {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let monochrome = self.size.display();
@@ -123,8 +165,9 @@ pub struct Stack {
 }
 
 macro_rules! stack {
-    // XXX: `stack!()` triggers unused_mut lint. I don't think vec![] does
-    // this—how does it manage?
+    // (a separate arm for the empty stack, lest `stack!()` leave
+    // `our_stack` needlessly mutable)
+    () => { Stack::new() };
     ($($triangle:expr),*) => {
         {
             let mut our_stack = Stack::new();
@@ -143,15 +186,20 @@ impl Stack {
         self.triangles.push(triangle);
     }
 
-    pub fn sample() -> Self {
+    /// from the ground up
+    pub fn triangles(&self) -> &[Triangle] {
+        &self.triangles
+    }
+
+    pub fn sample<R: Rng>(rng: &mut R) -> Self {
         // NOTE: A uniform distribution over heights is nonuniform over
         // possible stacks (because there are exponentially more taller
         // stacks), but that's OK, and we probably want a bias towards simpler
         // studies anyway
-        let height = 1 + random::<usize>() % 3;
+        let height = rng.gen_range(1, 4);
         let mut stack = Stack::new();
         for _ in 0..height {
-            stack.push(Triangle::sample());
+            stack.push(Triangle::sample(rng));
         }
         stack
     }
@@ -208,6 +256,10 @@ impl Study {
         self.stacks.push(stack);
     }
 
+    pub fn stacks(&self) -> &[Stack] {
+        &self.stacks
+    }
+
     pub fn color_count(&self, color: Color) -> usize {
         self.into_iter().filter(|t| { t.color == color }).count()
     }
@@ -232,16 +284,228 @@ impl Study {
         }
     }
 
-    pub fn sample() -> Self {
+    pub fn sample<R: Rng>(rng: &mut R) -> Self {
         // Again, a uniform distribution over stack count is nonuniform over
         // possible studies; we think it's fine!
-        let breadth = 1 + random::<usize>() % 3;
+        let breadth = rng.gen_range(1, 4);
         let mut study = Study::new();
         for _ in 0..breadth {
-            study.append(Stack::sample());
+            study.append(Stack::sample(rng));
         }
         study
     }
+
+    /// The study in compact notation: one stack at a time, separated by
+    /// `|`, each triangle as the initial of its color followed by its size,
+    /// from the ground up (e.g., `R3 B1 | G2 | Y1 Y1`).
+    pub fn to_notation(&self) -> String {
+        self.stacks.iter()
+            .map(|stack| {
+                stack.triangles.iter()
+                    .map(|triangle| triangle.to_notation())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    pub fn from_notation(notation: &str) -> Result<Self, String> {
+        let mut study = Study::new();
+        if notation.trim().is_empty() {
+            return Ok(study);
+        }
+        for stack_notation in notation.split('|') {
+            let mut stack = Stack::new();
+            for token in stack_notation.split_whitespace() {
+                stack.push(Triangle::from_notation(token)?);
+            }
+            if stack.triangles().is_empty() {
+                return Err(format!("empty stack in {:?} (expected \
+                                    triangles between each '|')",
+                                   notation));
+            }
+            study.append(stack);
+        }
+        Ok(study)
+    }
+
+    /// every study with exactly this many triangles (in any arrangement of
+    /// nonempty stacks)
+    pub fn enumerate(triangle_count: usize) -> Vec<Self> {
+        let triangles = Color::iter()
+            .flat_map(|&color| {
+                Size::iter().map(move |&size| Triangle::new(color, size))
+            })
+            .collect::<Vec<_>>();
+        let mut sequences: Vec<Vec<Triangle>> = vec![Vec::new()];
+        for _ in 0..triangle_count {
+            sequences = sequences.into_iter()
+                .flat_map(|sequence| {
+                    triangles.iter().map(move |&triangle| {
+                        let mut extended = sequence.clone();
+                        extended.push(triangle);
+                        extended
+                    })
+                })
+                .collect();
+        }
+
+        let mut studies = Vec::new();
+        // each bit of `breaks` says whether to start a new stack before the
+        // corresponding triangle (after the first)
+        let arrangements = if triangle_count == 0 {
+            1
+        } else {
+            1 << (triangle_count - 1)
+        };
+        for sequence in &sequences {
+            for breaks in 0..arrangements {
+                let mut study = Study::new();
+                let mut stack = Stack::new();
+                for (i, &triangle) in sequence.iter().enumerate() {
+                    if i > 0 && breaks & (1 << (i - 1)) != 0 {
+                        study.append(stack);
+                        stack = Stack::new();
+                    }
+                    stack.push(triangle);
+                }
+                if !stack.triangles.is_empty() {
+                    study.append(stack);
+                }
+                studies.push(study);
+            }
+        }
+        studies
+    }
+}
+
+
+/// a small change to a study, for searching locally among studies
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Mutation {
+    AddTriangle,
+    RemoveTriangle,
+    Recolor,
+    Resize,
+    /// move the top triangle of one stack onto another
+    MoveTriangle,
+    AddStack,
+    RemoveStack,
+}
+
+pub static MUTATIONS: [Mutation; 7] = [
+    Mutation::AddTriangle, Mutation::RemoveTriangle, Mutation::Recolor,
+    Mutation::Resize, Mutation::MoveTriangle, Mutation::AddStack,
+    Mutation::RemoveStack];
+
+impl Study {
+    /// (stack index, index within the stack) of a triangle chosen uniformly
+    fn sample_position<R: Rng>(&self, rng: &mut R) -> (usize, usize) {
+        let mut index = rng.gen_range(0, self.into_iter().count());
+        for (stack_index, stack) in self.stacks.iter().enumerate() {
+            if index < stack.triangles.len() {
+                return (stack_index, index);
+            }
+            index -= stack.triangles.len();
+        }
+        unreachable!("index should be within the study");
+    }
+
+    /// The study with a mutation applied (at random, where there's a choice
+    /// of where or how), or `None` if it doesn't apply: we don't remove the
+    /// last triangle or stack, nor move a triangle without another stack to
+    /// move it to.
+    pub fn mutated<R: Rng>(&self, mutation: Mutation, rng: &mut R)
+                           -> Option<Self> {
+        let triangle_count = self.into_iter().count();
+        let mut study = self.clone();
+        match mutation {
+            Mutation::AddTriangle => {
+                if study.stacks.is_empty() {
+                    study.append(Stack::new());
+                }
+                let stack_index = rng.gen_range(0, study.stacks.len());
+                let stack = &mut study.stacks[stack_index].triangles;
+                let position = rng.gen_range(0, stack.len() + 1);
+                stack.insert(position, Triangle::sample(rng));
+            },
+            Mutation::RemoveTriangle => {
+                if triangle_count < 2 {
+                    return None;
+                }
+                let (stack_index, index) = study.sample_position(rng);
+                study.stacks[stack_index].triangles.remove(index);
+                if study.stacks[stack_index].triangles.is_empty() {
+                    study.stacks.remove(stack_index);
+                }
+            },
+            Mutation::Recolor => {
+                if triangle_count == 0 {
+                    return None;
+                }
+                let (stack_index, index) = study.sample_position(rng);
+                let triangle = &mut study.stacks[stack_index]
+                    .triangles[index];
+                let others = Color::iter()
+                    .filter(|&&color| color != triangle.color)
+                    .collect::<Vec<_>>();
+                triangle.color = *others[rng.gen_range(0, others.len())];
+            },
+            Mutation::Resize => {
+                if triangle_count == 0 {
+                    return None;
+                }
+                let (stack_index, index) = study.sample_position(rng);
+                let triangle = &mut study.stacks[stack_index]
+                    .triangles[index];
+                let others = Size::iter()
+                    .filter(|&&size| size != triangle.size)
+                    .collect::<Vec<_>>();
+                triangle.size = *others[rng.gen_range(0, others.len())];
+            },
+            Mutation::MoveTriangle => {
+                if study.stacks.len() < 2 {
+                    return None;
+                }
+                let from = rng.gen_range(0, study.stacks.len());
+                let mut to = rng.gen_range(0, study.stacks.len() - 1);
+                if to >= from {
+                    to += 1;
+                }
+                let triangle = study.stacks[from].triangles.pop()
+                    .expect("stacks should be nonempty");
+                study.stacks[to].push(triangle);
+                if study.stacks[from].triangles.is_empty() {
+                    study.stacks.remove(from);
+                }
+            },
+            Mutation::AddStack => {
+                let mut stack = Stack::new();
+                stack.push(Triangle::sample(rng));
+                let position = rng.gen_range(0, study.stacks.len() + 1);
+                study.stacks.insert(position, stack);
+            },
+            Mutation::RemoveStack => {
+                if study.stacks.len() < 2 {
+                    return None;
+                }
+                let index = rng.gen_range(0, study.stacks.len());
+                study.stacks.remove(index);
+            }
+        }
+        Some(study)
+    }
+
+    /// the study with a randomly chosen applicable mutation applied
+    pub fn sample_neighbor<R: Rng>(&self, rng: &mut R) -> Self {
+        loop {
+            let mutation = MUTATIONS[rng.gen_range(0, MUTATIONS.len())];
+            if let Some(neighbor) = self.mutated(mutation, rng) {
+                return neighbor;
+            }
+        }
+    }
 }
 
 pub struct StudyIter<'a> {
@@ -290,6 +554,7 @@ impl<'a> IntoIterator for &'a Study {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::thread_rng;
 
     #[test]
     fn concerning_study_iteration() {
@@ -316,4 +581,83 @@ mod tests {
         assert_eq!(4, study.color_count(Color::Blue));
     }
 
+    #[test]
+    fn concerning_notation() {
+        let study = study!(stack!(Triangle::new(Color::Red, Size::Three),
+                                  Triangle::new(Color::Blue, Size::One)),
+                           stack!(Triangle::new(Color::Green, Size::Two)),
+                           stack!(Triangle::new(Color::Yellow, Size::One),
+                                  Triangle::new(Color::Yellow, Size::One)));
+        assert_eq!("R3 B1 | G2 | Y1 Y1", study.to_notation());
+        assert_eq!(Ok(study.clone()),
+                   Study::from_notation(&study.to_notation()));
+        assert_eq!(Ok(study), Study::from_notation(" r3 b1|G2 |y1   Y1 "));
+        assert_eq!(Ok(Study::new()), Study::from_notation(""));
+
+        assert_eq!(Err("unknown color 'P' in \"P1\" (expected R, B, G, or \
+                        Y)".to_owned()),
+                   Study::from_notation("R1 | P1"));
+        assert_eq!(Err("unknown size \"4\" in \"R4\" (expected 1, 2, or \
+                        3)".to_owned()),
+                   Study::from_notation("R4"));
+        assert!(Study::from_notation("G").is_err());
+        assert_eq!(Err("empty stack in \"R1 |\" (expected triangles \
+                        between each '|')".to_owned()),
+                   Study::from_notation("R1 |"));
+        assert!(Study::from_notation("R1 | | G2").is_err());
+        assert!(Study::from_notation("| B3").is_err());
+    }
+
+    #[test]
+    fn concerning_the_enumeration_of_studies() {
+        assert_eq!(vec![Study::new()], Study::enumerate(0));
+        assert_eq!(12, Study::enumerate(1).len());
+        let pairs = Study::enumerate(2);
+        // (two triangles, stacked or side by side)
+        assert_eq!(2 * 12 * 12, pairs.len());
+        assert!(pairs.iter().all(|study| study.into_iter().count() == 2));
+        assert!(pairs.contains(
+            &study!(stack!(Triangle::new(Color::Red, Size::One)),
+                    stack!(Triangle::new(Color::Yellow, Size::Three)))));
+    }
+
+    #[test]
+    fn concerning_mutations() {
+        let mut rng = thread_rng();
+        let study = Study::from_notation("R3 B1 | G2").unwrap();
+        let pips = study.pip_count();
+        for _ in 0..100 {
+            let added = study.mutated(Mutation::AddTriangle, &mut rng)
+                .unwrap();
+            assert_eq!(4, added.into_iter().count());
+            assert_eq!(2, added.stacks().len());
+            let removed = study.mutated(Mutation::RemoveTriangle, &mut rng)
+                .unwrap();
+            assert_eq!(2, removed.into_iter().count());
+            let recolored = study.mutated(Mutation::Recolor, &mut rng)
+                .unwrap();
+            assert_eq!(pips, recolored.pip_count());
+            assert!(recolored != study);
+            let resized = study.mutated(Mutation::Resize, &mut rng).unwrap();
+            assert!(resized.pip_count() != pips);
+            let moved = study.mutated(Mutation::MoveTriangle, &mut rng)
+                .unwrap();
+            assert!(moved == Study::from_notation("R3 B1 G2").unwrap() ||
+                    moved == Study::from_notation("R3 | G2 B1").unwrap());
+            let unstacked = study.mutated(Mutation::RemoveStack, &mut rng)
+                .unwrap();
+            assert_eq!(1, unstacked.stacks().len());
+            let stacked = study.mutated(Mutation::AddStack, &mut rng)
+                .unwrap();
+            assert_eq!(3, stacked.stacks().len());
+            assert!(study.sample_neighbor(&mut rng) != study);
+        }
+
+        let lonely = Study::from_notation("Y1").unwrap();
+        for &mutation in &[Mutation::RemoveTriangle, Mutation::MoveTriangle,
+                           Mutation::RemoveStack] {
+            assert_eq!(None, lonely.mutated(mutation, &mut rng));
+        }
+    }
+
 }